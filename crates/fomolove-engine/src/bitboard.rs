@@ -0,0 +1,287 @@
+//! 4x4 board packed in a `u64`, one nibble per cell exponent, row `i` in bits
+//! `16 * i..16 * i + 16` and column `j` in the nibble `j` of its row.
+//!
+//! This is the fast path of [`Board`] under the `row-table` feature while
+//! every tile is at most 32768: a move slides four packed rows looked up in a
+//! 256KB table generated at compile time from [`Board::merge_line`], so both
+//! paths share the exact same rule. Without the table a row costs the same
+//! `merge_line` as the scalar path plus the packing, so the on-chain program
+//! keeps the scalar path.
+
+use crate::{tile_value, Board, Direction, MoveOutcome, MAX_BOARD_SIZE};
+
+/// Side of the boards the bitboard can hold.
+pub const BITBOARD_SIZE: usize = 4;
+
+/// Largest exponent a nibble can hold.
+pub const NIBBLE_MAX_EXPONENT: u8 = 15;
+
+const ROW_COUNT: usize = 1 << 16;
+const ROW_MASK: u64 = 0xFFFF;
+const NIBBLE_LOW_BITS: u64 = 0x1111_1111_1111_1111;
+
+const RESULT_MASK: u32 = 0xFFFF;
+const MERGE_A_SHIFT: u32 = 16;
+const MERGE_B_SHIFT: u32 = 20;
+/// Set when a merge creates a tile above `NIBBLE_MAX_EXPONENT`.
+const OVERFLOW: u32 = 1 << 24;
+
+/// Row slid towards nibble 0, indexed by the row itself.
+#[allow(long_running_const_eval)]
+static ROW_LEFT: [u32; ROW_COUNT] = build_row_table();
+
+const fn build_row_table() -> [u32; ROW_COUNT] {
+    let mut table = [0u32; ROW_COUNT];
+    let mut row = 0;
+    while row < ROW_COUNT {
+        table[row] = row_entry(row);
+        row += 1;
+    }
+    table
+}
+
+fn row_left(row: u64) -> u32 {
+    ROW_LEFT[row as usize]
+}
+
+/// Slides a packed row towards nibble 0. The entry packs the resulting row
+/// (bits 0..16), the exponents of up to two merged tiles (bits 16..20 and
+/// 20..24) and `OVERFLOW`.
+const fn row_entry(row: usize) -> u32 {
+    let mut line = [0u8; MAX_BOARD_SIZE];
+    let mut i = 0;
+    while i < BITBOARD_SIZE {
+        line[i] = ((row >> (4 * i)) & 0xF) as u8;
+        i += 1;
+    }
+
+    let (merged, score, max_tile, _) = Board::merge_line(line, BITBOARD_SIZE);
+
+    let mut entry = 0u32;
+    i = 0;
+    while i < BITBOARD_SIZE {
+        if merged[i] > NIBBLE_MAX_EXPONENT {
+            entry |= OVERFLOW;
+        }
+        entry |= ((merged[i] & 0xF) as u32) << (4 * i);
+        i += 1;
+    }
+
+    // A row holds at most two merges, the score is the sum of both tiles
+    if entry & OVERFLOW == 0 {
+        let other = score - tile_value(max_tile);
+        let other_tile = if other == 0 { 0 } else { other.trailing_zeros() };
+        entry |= (max_tile as u32) << MERGE_A_SHIFT;
+        entry |= other_tile << MERGE_B_SHIFT;
+    }
+
+    entry
+}
+
+const fn reverse_row(row: u64) -> u64 {
+    ((row >> 12) & 0x000F) | ((row >> 4) & 0x00F0) | ((row << 4) & 0x0F00) | ((row << 12) & 0xF000)
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BitBoard(pub u64);
+
+impl BitBoard {
+    /// Packs a board, `None` when it is not 4x4 or a tile does not fit a nibble.
+    pub fn from_board(board: &Board) -> Option<Self> {
+        if board.size() != BITBOARD_SIZE {
+            return None;
+        }
+
+        let mut bits = 0u64;
+        for (index, &tile) in board.cells().iter().enumerate() {
+            if tile > NIBBLE_MAX_EXPONENT {
+                return None;
+            }
+            bits |= (tile as u64) << (4 * index);
+        }
+        Some(Self(bits))
+    }
+
+    pub fn to_board(self) -> Board {
+        let mut board = Board::new(BITBOARD_SIZE);
+        for row in 0..BITBOARD_SIZE {
+            for col in 0..BITBOARD_SIZE {
+                board.set(row, col, self.cell(BITBOARD_SIZE * row + col));
+            }
+        }
+        board
+    }
+
+    /// Exponent at `index`, in row-major order.
+    pub fn cell(self, index: usize) -> u8 {
+        ((self.0 >> (4 * index)) & 0xF) as u8
+    }
+
+    pub fn transpose(self) -> Self {
+        let x = self.0;
+        let a1 = x & 0xF0F0_0F0F_F0F0_0F0F;
+        let a2 = x & 0x0000_F0F0_0000_F0F0;
+        let a3 = x & 0x0F0F_0000_0F0F_0000;
+        let a = a1 | (a2 << 12) | (a3 >> 12);
+        let b1 = a & 0xFF00_FF00_00FF_00FF;
+        let b2 = a & 0x00FF_00FF_0000_0000;
+        let b3 = a & 0x0000_0000_FF00_FF00;
+        Self(b1 | (b2 >> 24) | (b3 << 24))
+    }
+
+    /// Number of empty cells, counted with a nibble-wise OR fold.
+    pub fn empty_count(self) -> u32 {
+        let mut x = self.0;
+        x |= x >> 2;
+        x |= x >> 1;
+        (!x & NIBBLE_LOW_BITS).count_ones()
+    }
+
+    /// Row-major index of the `n`th empty cell.
+    pub fn nth_empty(self, n: usize) -> Option<usize> {
+        (0..BITBOARD_SIZE * BITBOARD_SIZE)
+            .filter(|&index| self.cell(index) == 0)
+            .nth(n)
+    }
+
+    /// Slides every row towards nibble 0 (`reverse` mirrors the rows first).
+    fn slide_rows(self, reverse: bool) -> Option<(Self, MoveOutcome)> {
+        let mut bits = 0u64;
+        let mut outcome = MoveOutcome::default();
+
+        for i in 0..BITBOARD_SIZE {
+            let mut row = (self.0 >> (16 * i)) & ROW_MASK;
+            if reverse {
+                row = reverse_row(row);
+            }
+
+            let entry = row_left(row);
+            if entry & OVERFLOW != 0 {
+                return None;
+            }
+
+            let mut result = (entry & RESULT_MASK) as u64;
+            if reverse {
+                result = reverse_row(result);
+            }
+            bits |= result << (16 * i);
+
+            let merge_a = ((entry >> MERGE_A_SHIFT) & 0xF) as u8;
+            let merge_b = ((entry >> MERGE_B_SHIFT) & 0xF) as u8;
+            outcome.score += tile_value(merge_a) + tile_value(merge_b);
+            // Merged tiles are at least a 4, exponent 0 marks a missing merge
+            outcome.merges += (merge_a != 0) as u8 + (merge_b != 0) as u8;
+            if merge_a > outcome.max_tile {
+                outcome.max_tile = merge_a;
+            }
+        }
+
+        Some((Self(bits), outcome))
+    }
+
+    /// Applies a move, `None` when a merge does not fit a nibble and the
+    /// scalar path has to take over.
+    pub fn apply_move(&mut self, direction: Direction) -> Option<MoveOutcome> {
+        let (bits, mut outcome) = match direction {
+            Direction::Left => self.slide_rows(false)?,
+            Direction::Right => self.slide_rows(true)?,
+            Direction::Up => {
+                let (bits, outcome) = self.transpose().slide_rows(false)?;
+                (bits.transpose(), outcome)
+            }
+            Direction::Down => {
+                let (bits, outcome) = self.transpose().slide_rows(true)?;
+                (bits.transpose(), outcome)
+            }
+        };
+
+        outcome.moved = bits != *self;
+        *self = bits;
+        Some(outcome)
+    }
+
+    /// Whether any move changes the board. A full row is stuck in both
+    /// directions exactly when sliding it left is a no-op.
+    pub fn can_move(self) -> bool {
+        if self.empty_count() > 0 {
+            return true;
+        }
+
+        let transposed = self.transpose();
+        (0..BITBOARD_SIZE).any(|i| {
+            [self, transposed].iter().any(|board| {
+                let row = (board.0 >> (16 * i)) & ROW_MASK;
+                let entry = row_left(row);
+                entry & OVERFLOW != 0 || (entry & RESULT_MASK) as u64 != row
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SeededEntropy;
+
+    fn assert_matches_scalar(bits: BitBoard, direction: Direction) {
+        let mut scalar = bits.to_board();
+        let expected = scalar.apply_move_scalar(direction);
+
+        let mut fast = bits;
+        match fast.apply_move(direction) {
+            Some(outcome) => {
+                assert_eq!(outcome, expected, "{:#018x} {:?}", bits.0, direction);
+                assert_eq!(fast.to_board(), scalar, "{:#018x} {:?}", bits.0, direction);
+            }
+            None => assert!(scalar.cells().iter().any(|&tile| tile > NIBBLE_MAX_EXPONENT)),
+        }
+    }
+
+    #[test]
+    fn row_table_matches_row_entries() {
+        for (row, &entry) in ROW_LEFT.iter().enumerate() {
+            assert_eq!(entry, row_entry(row), "row {:#06x}", row);
+        }
+    }
+
+    #[test]
+    fn every_row_matches_the_scalar_move() {
+        for row in 0..ROW_COUNT as u64 {
+            assert_matches_scalar(BitBoard(row), Direction::Left);
+            assert_matches_scalar(BitBoard(row << 48), Direction::Right);
+        }
+    }
+
+    #[test]
+    fn transpose_swaps_rows_and_columns() {
+        let mut entropy = SeededEntropy::new(7);
+        for _ in 0..1000 {
+            let bits = BitBoard(entropy.next_u64());
+            let transposed = bits.transpose();
+            for row in 0..BITBOARD_SIZE {
+                for col in 0..BITBOARD_SIZE {
+                    assert_eq!(transposed.cell(BITBOARD_SIZE * col + row), bits.cell(BITBOARD_SIZE * row + col));
+                }
+            }
+            assert_eq!(transposed.transpose(), bits);
+        }
+    }
+
+    #[test]
+    fn random_boards_match_the_scalar_move() {
+        let mut entropy = SeededEntropy::new(2048);
+        for _ in 0..10_000 {
+            // Clear about half of the cells so boards both slide and merge
+            let bits = BitBoard(entropy.next_u64() & entropy.next_u64() & 0x7777_7777_7777_7777);
+            for direction in Direction::ALL {
+                assert_matches_scalar(bits, direction);
+            }
+        }
+        for _ in 0..10_000 {
+            let bits = BitBoard(entropy.next_u64());
+            for direction in Direction::ALL {
+                assert_matches_scalar(bits, direction);
+            }
+        }
+    }
+}
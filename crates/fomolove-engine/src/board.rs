@@ -0,0 +1,668 @@
+#[cfg(feature = "row-table")]
+use crate::BitBoard;
+use crate::{
+    blocker_lifetime, blocker_tile, cell_exponent, merge_tiles, tile_kind, Entropy, SpecialTiles, TileKind,
+    SPECIAL_CHANCE_SCALE,
+};
+
+pub const MIN_BOARD_SIZE: usize = 3;
+pub const MAX_BOARD_SIZE: usize = 8;
+pub const DEFAULT_BOARD_SIZE: usize = 4;
+pub const MAX_CELL_COUNT: usize = MAX_BOARD_SIZE * MAX_BOARD_SIZE;
+
+/// Largest exponent a tile can reach, its value still fits the `u32` top tile
+/// stored by the program.
+pub const MAX_TILE_EXPONENT: u8 = 31;
+
+pub const fn is_valid_board_size(size: usize) -> bool {
+    size >= MIN_BOARD_SIZE && size <= MAX_BOARD_SIZE
+}
+
+/// Real value of a tile stored as its log2, 0 for an empty cell.
+pub const fn tile_value(exponent: u8) -> u64 {
+    if exponent == 0 {
+        0
+    } else {
+        1 << exponent
+    }
+}
+
+/// Exponent of a tile value, 0 for an empty cell. `value` must be a power of two.
+pub const fn tile_exponent(value: u64) -> u8 {
+    if value == 0 {
+        0
+    } else {
+        value.trailing_zeros() as u8
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+}
+
+/// What a single move did to the board.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MoveOutcome {
+    pub moved: bool,
+    /// Sum of the real values of the tiles created by merges.
+    pub score: u64,
+    /// Exponent of the largest tile created by a merge, 0 when nothing merged.
+    pub max_tile: u8,
+    /// Number of merges over every line.
+    pub merges: u8,
+}
+
+/// Empty cells of a board in row-major order.
+#[derive(Clone, Copy, Debug)]
+pub struct EmptyCells {
+    cells: [(u8, u8); MAX_CELL_COUNT],
+    len: usize,
+}
+
+impl EmptyCells {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, index: usize) -> Option<(usize, usize)> {
+        if index < self.len {
+            let (x, y) = self.cells[index];
+            Some((x as usize, y as usize))
+        } else {
+            None
+        }
+    }
+}
+
+/// A square 2048 board of `MIN_BOARD_SIZE` to `MAX_BOARD_SIZE` cells per
+/// side. Cells hold tile exponents: 0 is empty, 1 is a 2, 11 a 2048. Special
+/// tiles set the high bits of the cell, see [`crate::tile`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Board {
+    size: u8,
+    /// Row-major, only the first `size * size` cells are used.
+    cells: [u8; MAX_CELL_COUNT],
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self::new(DEFAULT_BOARD_SIZE)
+    }
+}
+
+impl Board {
+    /// Empty board, `size` must satisfy [`is_valid_board_size`].
+    pub const fn new(size: usize) -> Self {
+        assert!(is_valid_board_size(size), "invalid board size");
+        Self {
+            size: size as u8,
+            cells: [0; MAX_CELL_COUNT],
+        }
+    }
+
+    /// Board from row-major exponents, `None` when `cells` is not a valid square.
+    pub fn from_cells(size: usize, cells: &[u8]) -> Option<Self> {
+        if !is_valid_board_size(size) || cells.len() != size * size {
+            return None;
+        }
+
+        let mut board = Self::new(size);
+        board.cells[..cells.len()].copy_from_slice(cells);
+        Some(board)
+    }
+
+    pub fn size(&self) -> usize {
+        self.size as usize
+    }
+
+    /// Row-major exponents.
+    pub fn cells(&self) -> &[u8] {
+        &self.cells[..self.size() * self.size()]
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> u8 {
+        self.cells[row * self.size() + col]
+    }
+
+    pub fn set(&mut self, row: usize, col: usize, tile: u8) {
+        let size = self.size();
+        self.cells[row * size + col] = tile;
+    }
+
+    /// Slides the first `len` cells of a line towards index 0 and merges
+    /// equal neighbours once.
+    ///
+    /// Returns the new line, the score gained, the exponent of the largest
+    /// merged tile and the number of merges. Tiles at `MAX_TILE_EXPONENT` do not merge. This is a
+    /// `const fn` so the bitboard rows are computed from the same rule.
+    pub const fn merge_line(
+        line: [u8; MAX_BOARD_SIZE],
+        len: usize,
+    ) -> ([u8; MAX_BOARD_SIZE], u64, u8, u8) {
+        let mut i = 0;
+        while i < len {
+            if !matches!(tile_kind(line[i]), TileKind::Normal) {
+                return Self::merge_special_line(line, len);
+            }
+            i += 1;
+        }
+
+        let mut new_row = [0u8; MAX_BOARD_SIZE];
+        let mut pos = 0;
+        i = 0;
+        while i < len {
+            if line[i] != 0 {
+                new_row[pos] = line[i];
+                pos += 1;
+            }
+            i += 1;
+        }
+
+        let mut score_increment = 0;
+        let mut max_tile = 0;
+        let mut merges = 0;
+        i = 0;
+        while i + 1 < len {
+            if new_row[i] == new_row[i + 1] && new_row[i] != 0 && new_row[i] < MAX_TILE_EXPONENT {
+                let new_tile = new_row[i] + 1;
+                new_row[i] = new_tile;
+                new_row[i + 1] = 0;
+                score_increment += tile_value(new_tile);
+                merges += 1;
+
+                if new_tile > max_tile {
+                    max_tile = new_tile;
+                }
+            }
+            i += 1;
+        }
+
+        let mut final_row = [0u8; MAX_BOARD_SIZE];
+        pos = 0;
+        i = 0;
+        while i < len {
+            if new_row[i] != 0 {
+                final_row[pos] = new_row[i];
+                pos += 1;
+            }
+            i += 1;
+        }
+
+        (final_row, score_increment, max_tile, merges)
+    }
+
+    /// [`Board::merge_line`] for a line holding special tiles. Blockers keep
+    /// their cell and split the line into segments that slide on their own,
+    /// wildcards and multipliers merge as [`merge_tiles`] says.
+    const fn merge_special_line(
+        line: [u8; MAX_BOARD_SIZE],
+        len: usize,
+    ) -> ([u8; MAX_BOARD_SIZE], u64, u8, u8) {
+        let mut final_row = [0u8; MAX_BOARD_SIZE];
+        let mut score_increment = 0;
+        let mut max_tile = 0;
+        let mut merges = 0;
+
+        let mut start = 0;
+        while start < len {
+            let mut end = start;
+            while end < len && !matches!(tile_kind(line[end]), TileKind::Blocker) {
+                end += 1;
+            }
+
+            // Last tile placed in the segment that can still take a merge
+            let mut pending = 0u8;
+            let mut pos = start;
+            let mut i = start;
+            while i < end {
+                let tile = line[i];
+                i += 1;
+                if tile == 0 {
+                    continue;
+                }
+
+                if pending != 0 {
+                    if let Some((new_tile, score)) = merge_tiles(pending, tile) {
+                        final_row[pos] = new_tile;
+                        pos += 1;
+                        pending = 0;
+                        score_increment += score;
+                        merges += 1;
+
+                        if new_tile > max_tile {
+                            max_tile = new_tile;
+                        }
+                        continue;
+                    }
+
+                    final_row[pos] = pending;
+                    pos += 1;
+                }
+                pending = tile;
+            }
+            if pending != 0 {
+                final_row[pos] = pending;
+            }
+
+            if end < len {
+                final_row[end] = line[end];
+            }
+            start = end + 1;
+        }
+
+        (final_row, score_increment, max_tile, merges)
+    }
+
+    /// Cell of the `index`th line at `offset` positions from the edge the
+    /// tiles slide towards.
+    fn line_cell(&self, direction: Direction, index: usize, offset: usize) -> (usize, usize) {
+        let last = self.size() - 1;
+        match direction {
+            Direction::Left => (index, offset),
+            Direction::Right => (index, last - offset),
+            Direction::Up => (offset, index),
+            Direction::Down => (last - offset, index),
+        }
+    }
+
+    /// Applies a move in place. `moved` is false when the board is unchanged.
+    pub fn apply_move(&mut self, direction: Direction) -> MoveOutcome {
+        #[cfg(feature = "row-table")]
+        if let Some(mut bits) = BitBoard::from_board(self) {
+            if let Some(outcome) = bits.apply_move(direction) {
+                *self = bits.to_board();
+                return outcome;
+            }
+        }
+        self.apply_move_scalar(direction)
+    }
+
+    /// Line by line form of [`Board::apply_move`], the only one without the
+    /// `row-table` feature. With it, boards that are not 4x4 or hold a tile
+    /// that no longer fits the bitboard still take this path.
+    pub fn apply_move_scalar(&mut self, direction: Direction) -> MoveOutcome {
+        let size = self.size();
+        let old_cells = self.cells;
+        let mut outcome = MoveOutcome::default();
+
+        for index in 0..size {
+            let mut line = [0u8; MAX_BOARD_SIZE];
+            for (offset, tile) in line.iter_mut().enumerate().take(size) {
+                let (row, col) = self.line_cell(direction, index, offset);
+                *tile = self.get(row, col);
+            }
+
+            let (line, score_increment, max_tile, merges) = Self::merge_line(line, size);
+
+            for (offset, &tile) in line.iter().enumerate().take(size) {
+                let (row, col) = self.line_cell(direction, index, offset);
+                self.set(row, col, tile);
+            }
+
+            outcome.score += score_increment;
+            outcome.merges += merges;
+            if max_tile > outcome.max_tile {
+                outcome.max_tile = max_tile;
+            }
+        }
+
+        outcome.moved = old_cells != self.cells;
+        if outcome.moved {
+            self.tick_blockers();
+        }
+        outcome
+    }
+
+    /// Counts a move down on every blocker, removing the ones that expire.
+    fn tick_blockers(&mut self) {
+        let size = self.size();
+        for cell in self.cells[..size * size].iter_mut() {
+            match blocker_lifetime(*cell) {
+                0 => {}
+                1 => *cell = 0,
+                lifetime => *cell = blocker_tile(lifetime - 1),
+            }
+        }
+    }
+
+    fn has_blocker(&self) -> bool {
+        self.cells()
+            .iter()
+            .any(|&cell| matches!(tile_kind(cell), TileKind::Blocker))
+    }
+
+    pub fn empty_cells(&self) -> EmptyCells {
+        let mut empty = EmptyCells {
+            cells: [(0, 0); MAX_CELL_COUNT],
+            len: 0,
+        };
+        for i in 0..self.size() {
+            for j in 0..self.size() {
+                if self.get(i, j) == 0 {
+                    empty.cells[empty.len] = (i as u8, j as u8);
+                    empty.len += 1;
+                }
+            }
+        }
+        empty
+    }
+
+    /// Spawns a 2 or a 4 on an empty cell chosen by `entropy`.
+    ///
+    /// Returns the exponent of the spawned tile, or `None` when the board is full.
+    pub fn add_new_tile<E: Entropy>(&mut self, entropy: &mut E) -> Option<u8> {
+        self.spawn_tile(entropy, &SpecialTiles::NONE)
+    }
+
+    /// Spawns a tile on an empty cell chosen by `entropy`, special with the
+    /// chances of `special_tiles`.
+    ///
+    /// Returns the exponent of the spawned tile, encoded as a special tile
+    /// when one is drawn, or `None` when the board is full.
+    pub fn spawn_tile<E: Entropy>(
+        &mut self,
+        entropy: &mut E,
+        special_tiles: &SpecialTiles,
+    ) -> Option<u8> {
+        let (x, y, rand_index) = self.pick_empty_cell(entropy)?;
+
+        let exponent = if entropy.spawn_four(rand_index) { 2 } else { 1 };
+        // Vanilla rules draw nothing more, so their spawns stay unchanged
+        let new_tile = if special_tiles.is_enabled() {
+            let roll = entropy.special_roll(rand_index) % SPECIAL_CHANCE_SCALE;
+            special_tiles.spawned_cell(roll, exponent)
+        } else {
+            exponent
+        };
+        self.set(x, y, new_tile);
+
+        Some(new_tile)
+    }
+
+    /// Empty cell chosen by `entropy`, with its index among the empty cells.
+    fn pick_empty_cell<E: Entropy>(&self, entropy: &mut E) -> Option<(usize, usize, usize)> {
+        #[cfg(feature = "row-table")]
+        if let Some(bits) = BitBoard::from_board(self) {
+            let empty_count = bits.empty_count() as usize;
+            if empty_count == 0 {
+                return None;
+            }
+
+            let rand_index = entropy.pick_cell(self, empty_count) % empty_count;
+            let cell = bits.nth_empty(rand_index)?;
+            return Some((cell / self.size(), cell % self.size(), rand_index));
+        }
+
+        let empty_tiles = self.empty_cells();
+        if empty_tiles.is_empty() {
+            return None;
+        }
+
+        let rand_index = entropy.pick_cell(self, empty_tiles.len()) % empty_tiles.len();
+        let (x, y) = empty_tiles.get(rand_index)?;
+        Some((x, y, rand_index))
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        #[cfg(feature = "row-table")]
+        if let Some(bits) = BitBoard::from_board(self) {
+            return !bits.can_move();
+        }
+
+        // Blockers can wall off empty cells, only trying every move is exact
+        if self.has_blocker() {
+            return Direction::ALL
+                .iter()
+                .all(|&direction| !self.clone().apply_move_scalar(direction).moved);
+        }
+
+        let size = self.size();
+        for i in 0..size {
+            for j in 0..size {
+                let tile = self.get(i, j);
+                if tile == 0 {
+                    return false;
+                }
+                if j < size - 1 && merge_tiles(tile, self.get(i, j + 1)).is_some() {
+                    return false;
+                }
+                if i < size - 1 && merge_tiles(tile, self.get(i + 1, j)).is_some() {
+                    return false;
+                }
+            }
+        }
+        true
+    }
+
+    /// Exponent of the largest tile on the board.
+    pub fn max_tile(&self) -> u8 {
+        self.cells()
+            .iter()
+            .map(|&cell| cell_exponent(cell))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{multiplier_tile, wildcard_tile, SeededEntropy};
+
+    fn board(rows: [[u8; 4]; 4]) -> Board {
+        let mut board = Board::new(4);
+        for (row, cells) in rows.iter().enumerate() {
+            for (col, &cell) in cells.iter().enumerate() {
+                board.set(row, col, cell);
+            }
+        }
+        board
+    }
+
+    #[test]
+    fn merge_line_merges_each_tile_once() {
+        let (line, score, max_tile, merges) = Board::merge_line([1, 1, 1, 1, 0, 0, 0, 0], 4);
+        assert_eq!(&line[..4], &[2, 2, 0, 0]);
+        assert_eq!((score, max_tile, merges), (8, 2, 2));
+
+        let (line, score, _, merges) = Board::merge_line([2, 1, 1, 0, 0, 0, 0, 0], 4);
+        assert_eq!(&line[..4], &[2, 2, 0, 0]);
+        assert_eq!((score, merges), (4, 1));
+
+        let (line, score, _, merges) = Board::merge_line([0, 3, 0, 3, 0, 0, 0, 0], 4);
+        assert_eq!(&line[..4], &[4, 0, 0, 0]);
+        assert_eq!((score, merges), (16, 1));
+    }
+
+    #[test]
+    fn moves_slide_towards_their_edge() {
+        let start = board([[1, 0, 1, 0], [0, 2, 0, 0], [0, 0, 0, 0], [3, 0, 0, 3]]);
+
+        let mut left = start;
+        let outcome = left.apply_move(Direction::Left);
+        assert_eq!(left, board([[2, 0, 0, 0], [2, 0, 0, 0], [0, 0, 0, 0], [4, 0, 0, 0]]));
+        assert_eq!((outcome.moved, outcome.score, outcome.max_tile, outcome.merges), (true, 20, 4, 2));
+
+        let mut right = start;
+        right.apply_move(Direction::Right);
+        assert_eq!(right, board([[0, 0, 0, 2], [0, 0, 0, 2], [0, 0, 0, 0], [0, 0, 0, 4]]));
+
+        let mut up = start;
+        let outcome = up.apply_move(Direction::Up);
+        assert_eq!(up, board([[1, 2, 1, 3], [3, 0, 0, 0], [0, 0, 0, 0], [0, 0, 0, 0]]));
+        assert_eq!((outcome.score, outcome.merges), (0, 0));
+
+        let mut down = start;
+        down.apply_move(Direction::Down);
+        assert_eq!(down, board([[0, 0, 0, 0], [0, 0, 0, 0], [1, 0, 0, 0], [3, 2, 1, 3]]));
+    }
+
+    #[test]
+    fn blocked_move_leaves_the_board_unchanged() {
+        let mut start = board([[1, 2, 0, 0], [3, 4, 0, 0], [0, 0, 0, 0], [0, 0, 0, 0]]);
+        let outcome = start.apply_move(Direction::Left);
+        assert!(!outcome.moved);
+        assert_eq!(outcome, MoveOutcome::default());
+    }
+
+    #[test]
+    fn scalar_moves_match_on_larger_boards() {
+        let mut board = Board::new(5);
+        for col in 0..5 {
+            board.set(2, col, 1);
+        }
+        let outcome = board.apply_move(Direction::Right);
+        assert_eq!(&board.cells()[10..15], &[0, 0, 1, 2, 2]);
+        assert_eq!((outcome.score, outcome.merges), (8, 2));
+    }
+
+    #[test]
+    fn game_over_only_without_empty_cell_or_merge() {
+        let stuck = board([[1, 2, 1, 2], [2, 1, 2, 1], [1, 2, 1, 2], [2, 1, 2, 1]]);
+        assert!(stuck.is_game_over());
+
+        let mut mergeable = stuck;
+        mergeable.set(3, 3, 2);
+        assert!(!mergeable.is_game_over());
+
+        let mut open = stuck;
+        open.set(0, 0, 0);
+        assert!(!open.is_game_over());
+
+        let mut large = Board::new(3);
+        let cells = [1, 2, 1, 2, 1, 2, 1, 2, 1];
+        for (index, &cell) in cells.iter().enumerate() {
+            large.set(index / 3, index % 3, cell);
+        }
+        assert!(large.is_game_over());
+    }
+
+    #[test]
+    fn spawns_are_deterministic_for_a_seed() {
+        let mut a = Board::new(4);
+        let mut b = Board::new(4);
+        let mut entropy_a = SeededEntropy::new(42);
+        let mut entropy_b = SeededEntropy::new(42);
+        for _ in 0..16 {
+            assert_eq!(a.add_new_tile(&mut entropy_a), b.add_new_tile(&mut entropy_b));
+        }
+        assert_eq!(a, b);
+        assert!(a.empty_cells().is_empty());
+        assert!(a.cells().iter().all(|&cell| cell == 1 || cell == 2));
+        assert_eq!(a.add_new_tile(&mut entropy_a), None);
+
+        let mut other = Board::new(4);
+        let mut other_entropy = SeededEntropy::new(43);
+        for _ in 0..16 {
+            other.add_new_tile(&mut other_entropy);
+        }
+        assert_ne!(a, other);
+    }
+
+    #[test]
+    fn merges_above_the_bitboard_range() {
+        let (line, score, max_tile, merges) = Board::merge_line([15, 15, 16, 16, 0, 0, 0, 0], 4);
+        assert_eq!(&line[..4], &[16, 17, 0, 0]);
+        assert_eq!((score, max_tile, merges), ((1 << 16) + (1 << 17), 17, 2));
+
+        let mut start = board([[15, 15, 0, 0], [16, 0, 16, 0], [0, 0, 0, 0], [0, 0, 0, 0]]);
+        let mut scalar = start;
+        let outcome = start.apply_move(Direction::Left);
+        assert_eq!(start, board([[16, 0, 0, 0], [17, 0, 0, 0], [0, 0, 0, 0], [0, 0, 0, 0]]));
+        assert_eq!((outcome.score, outcome.max_tile), ((1 << 16) + (1 << 17), 17));
+        assert_eq!(scalar.apply_move_scalar(Direction::Left), outcome);
+    }
+
+    #[test]
+    fn tiles_stop_merging_at_the_maximum() {
+        let top = MAX_TILE_EXPONENT;
+        let (line, score, max_tile, _) = Board::merge_line([top - 1, top - 1, 0, 0, 0, 0, 0, 0], 4);
+        assert_eq!(&line[..4], &[top, 0, 0, 0]);
+        assert_eq!((score, max_tile), (u32::MAX as u64 / 2 + 1, top));
+
+        let (line, score, _, merges) = Board::merge_line([top, top, 0, 0, 0, 0, 0, 0], 4);
+        assert_eq!(&line[..4], &[top, top, 0, 0]);
+        assert_eq!((score, merges), (0, 0));
+        assert!(tile_value(MAX_TILE_EXPONENT) <= u32::MAX as u64);
+    }
+
+    #[test]
+    fn blockers_split_lines_into_segments() {
+        let blocker = blocker_tile(3);
+
+        let (line, score, max_tile, merges) = Board::merge_line([1, blocker, 1, 1, 0, 0, 0, 0], 4);
+        assert_eq!(&line[..4], &[1, blocker, 2, 0]);
+        assert_eq!((score, max_tile, merges), (4, 2, 1));
+
+        // Tiles do not slide nor merge through a blocker
+        let (line, score, _, _) = Board::merge_line([0, 1, blocker, 1, 0, 0, 0, 0], 4);
+        assert_eq!(&line[..4], &[1, 0, blocker, 1]);
+        assert_eq!(score, 0);
+    }
+
+    #[test]
+    fn wildcards_pair_with_any_tile_but_a_wildcard() {
+        let wildcard = wildcard_tile();
+
+        let (line, score, max_tile, merges) = Board::merge_line([wildcard, 0, 3, 0, 0, 0, 0, 0], 4);
+        assert_eq!(&line[..4], &[4, 0, 0, 0]);
+        assert_eq!((score, max_tile, merges), (16, 4, 1));
+
+        let (line, score, _, merges) = Board::merge_line([wildcard, wildcard, 0, 0, 0, 0, 0, 0], 4);
+        assert_eq!(&line[..4], &[wildcard, wildcard, 0, 0]);
+        assert_eq!((score, merges), (0, 0));
+
+        let (line, score, _, merges) = Board::merge_line([wildcard, wildcard, 2, 0, 0, 0, 0, 0], 4);
+        assert_eq!(&line[..4], &[wildcard, 3, 0, 0]);
+        assert_eq!((score, merges), (8, 1));
+    }
+
+    #[test]
+    fn multipliers_score_double() {
+        let (line, score, max_tile, _) = Board::merge_line([multiplier_tile(2), 2, 0, 0, 0, 0, 0, 0], 4);
+        assert_eq!(&line[..4], &[3, 0, 0, 0]);
+        assert_eq!((score, max_tile), (16, 3));
+
+        let (line, score, _, _) = Board::merge_line([wildcard_tile(), multiplier_tile(4), 0, 0, 0, 0, 0, 0], 4);
+        assert_eq!(&line[..4], &[5, 0, 0, 0]);
+        assert_eq!(score, 64);
+
+        // A multiplier only merges with a tile of its own exponent
+        let (line, score, _, _) = Board::merge_line([multiplier_tile(2), 3, 0, 0, 0, 0, 0, 0], 4);
+        assert_eq!(&line[..4], &[multiplier_tile(2), 3, 0, 0]);
+        assert_eq!(score, 0);
+    }
+
+    #[test]
+    fn blockers_expire_after_their_lifetime() {
+        let mut start = board([[blocker_tile(1), 0, 1, 0], [blocker_tile(3), 0, 0, 0], [0; 4], [0; 4]]);
+        start.tick_blockers();
+        assert_eq!(start, board([[0, 0, 1, 0], [blocker_tile(2), 0, 0, 0], [0; 4], [0; 4]]));
+
+        // Only moves that change the board count down
+        let mut blocked = board([[blocker_tile(2), 1, 0, 0], [0; 4], [0; 4], [0; 4]]);
+        assert!(!blocked.apply_move(Direction::Left).moved);
+        assert_eq!(blocked.get(0, 0), blocker_tile(2));
+
+        let outcome = blocked.apply_move(Direction::Right);
+        assert!(outcome.moved);
+        assert_eq!(blocked, board([[blocker_tile(1), 0, 0, 1], [0; 4], [0; 4], [0; 4]]));
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{Board, SPECIAL_CHANCE_SCALE};
+
+/// Source of randomness for tile spawns.
+///
+/// The program plugs in its on-chain source, off-chain tools can use
+/// [`SeededEntropy`] or replay recorded values.
+pub trait Entropy {
+    /// Picks the empty cell that receives the next tile, in `0..empty_count`.
+    fn pick_cell(&mut self, board: &Board, empty_count: usize) -> usize;
+
+    /// Whether the tile spawned for `cell_index` is a 4 instead of a 2.
+    fn spawn_four(&mut self, cell_index: usize) -> bool;
+
+    /// Draw in `0..SPECIAL_CHANCE_SCALE` that decides whether the tile spawned
+    /// for `cell_index` is special. Only drawn when special tiles are enabled.
+    fn special_roll(&mut self, cell_index: usize) -> u16;
+}
+
+impl<E: Entropy + ?Sized> Entropy for &mut E {
+    fn pick_cell(&mut self, board: &Board, empty_count: usize) -> usize {
+        (**self).pick_cell(board, empty_count)
+    }
+
+    fn spawn_four(&mut self, cell_index: usize) -> bool {
+        (**self).spawn_four(cell_index)
+    }
+
+    fn special_roll(&mut self, cell_index: usize) -> u16 {
+        (**self).special_roll(cell_index)
+    }
+}
+
+/// Deterministic splitmix64 generator for simulations.
+#[derive(Clone, Copy, Debug)]
+pub struct SeededEntropy {
+    state: u64,
+}
+
+impl SeededEntropy {
+    pub const fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl Entropy for SeededEntropy {
+    fn pick_cell(&mut self, _board: &Board, empty_count: usize) -> usize {
+        (self.next_u64() % empty_count as u64) as usize
+    }
+
+    fn spawn_four(&mut self, _cell_index: usize) -> bool {
+        self.next_u64() % 10 == 0
+    }
+
+    fn special_roll(&mut self, _cell_index: usize) -> u16 {
+        (self.next_u64() % SPECIAL_CHANCE_SCALE as u64) as u16
+    }
+}
@@ -0,0 +1,26 @@
+//! Pure 2048 rules used by the `fomolove-sonic` program.
+//!
+//! The crate is `no_std` and has no Solana dependencies so the exact same
+//! move, merge, spawn and game-over logic can run on-chain, in the frontend
+//! simulator and in off-chain tooling.
+#![no_std]
+
+pub mod board;
+pub use board::*;
+
+pub mod tile;
+pub use tile::*;
+
+pub mod scoring;
+pub use scoring::*;
+
+#[cfg(feature = "row-table")]
+pub mod bitboard;
+#[cfg(feature = "row-table")]
+pub use bitboard::*;
+
+pub mod entropy;
+pub use entropy::*;
+
+pub mod move_log;
+pub use move_log::*;
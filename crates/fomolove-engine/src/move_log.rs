@@ -0,0 +1,101 @@
+//! Packed move transcript: 2 bits per move, oldest move in the lowest bits
+//! of the first byte.
+
+use crate::Direction;
+
+pub const MOVES_PER_BYTE: usize = 4;
+
+impl Direction {
+    pub const fn to_bits(self) -> u8 {
+        match self {
+            Direction::Up => 0,
+            Direction::Down => 1,
+            Direction::Left => 2,
+            Direction::Right => 3,
+        }
+    }
+
+    pub const fn from_bits(bits: u8) -> Self {
+        match bits & 0b11 {
+            0 => Direction::Up,
+            1 => Direction::Down,
+            2 => Direction::Left,
+            _ => Direction::Right,
+        }
+    }
+}
+
+/// Bytes needed to store `move_count` moves.
+pub const fn move_log_len(move_count: usize) -> usize {
+    move_count.div_ceil(MOVES_PER_BYTE)
+}
+
+/// Writes the move at `index`. `log` must hold at least `move_log_len(index + 1)` bytes.
+pub fn write_move(log: &mut [u8], index: usize, direction: Direction) {
+    let shift = (index % MOVES_PER_BYTE) * 2;
+    let byte = &mut log[index / MOVES_PER_BYTE];
+    *byte = (*byte & !(0b11 << shift)) | (direction.to_bits() << shift);
+}
+
+pub fn read_move(log: &[u8], index: usize) -> Direction {
+    let shift = (index % MOVES_PER_BYTE) * 2;
+    Direction::from_bits(log[index / MOVES_PER_BYTE] >> shift)
+}
+
+/// Iterates over the first `move_count` moves of a packed log.
+pub fn decode_moves(log: &[u8], move_count: usize) -> impl Iterator<Item = Direction> + '_ {
+    (0..move_count).map(move |index| read_move(log, index))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn directions_round_trip_through_their_bits() {
+        for direction in Direction::ALL {
+            assert_eq!(Direction::from_bits(direction.to_bits()), direction);
+        }
+    }
+
+    #[test]
+    fn logs_round_trip() {
+        let moves = [
+            Direction::Up,
+            Direction::Right,
+            Direction::Down,
+            Direction::Left,
+            Direction::Left,
+            Direction::Up,
+            Direction::Right,
+        ];
+        let mut log = [0u8; 2];
+        assert_eq!(move_log_len(moves.len()), log.len());
+        for (index, &direction) in moves.iter().enumerate() {
+            write_move(&mut log, index, direction);
+        }
+
+        assert!(decode_moves(&log, moves.len()).eq(moves.iter().copied()));
+        assert_eq!(log[0], 0b10_01_11_00);
+    }
+
+    #[test]
+    fn rewriting_a_move_keeps_its_neighbours() {
+        let mut log = [0u8; 1];
+        for index in 0..4 {
+            write_move(&mut log, index, Direction::Right);
+        }
+        write_move(&mut log, 2, Direction::Up);
+        assert_eq!(read_move(&log, 1), Direction::Right);
+        assert_eq!(read_move(&log, 2), Direction::Up);
+        assert_eq!(read_move(&log, 3), Direction::Right);
+    }
+
+    #[test]
+    fn log_length_rounds_up() {
+        assert_eq!(move_log_len(0), 0);
+        assert_eq!(move_log_len(1), 1);
+        assert_eq!(move_log_len(4), 1);
+        assert_eq!(move_log_len(5), 2);
+    }
+}
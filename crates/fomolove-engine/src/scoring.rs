@@ -0,0 +1,127 @@
+//! Optional score bonuses on top of the merged tile values.
+//!
+//! A combo is a move with several merges, a streak a run of consecutive moves
+//! that merge. Both add a percentage of the move's merge score, so a move
+//! that merges nothing still scores nothing.
+
+/// Bonuses are percentages of the merge score of a move.
+pub const BONUS_SCALE: u64 = 100;
+
+/// Largest `combo_bonus` or `streak_bonus`, ten times the merge score.
+pub const MAX_SCORE_BONUS: u16 = 1000;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ScoreBonuses {
+    /// Percent added for every merge of a move beyond the first
+    pub combo_bonus: u16,
+    /// Percent added for every merging move right before this one
+    pub streak_bonus: u16,
+    /// Longest streak the bonus counts, 0 for no limit
+    pub streak_cap: u8,
+}
+
+impl ScoreBonuses {
+    /// Plain 2048 scoring.
+    pub const NONE: ScoreBonuses = ScoreBonuses {
+        combo_bonus: 0,
+        streak_bonus: 0,
+        streak_cap: 0,
+    };
+
+    pub const fn is_enabled(&self) -> bool {
+        self.combo_bonus != 0 || self.streak_bonus != 0
+    }
+
+    pub const fn is_valid(&self) -> bool {
+        self.combo_bonus <= MAX_SCORE_BONUS && self.streak_bonus <= MAX_SCORE_BONUS
+    }
+
+    /// Score of a move that merged `merges` times for `merge_score`, `streak`
+    /// being the number of merging moves right before it.
+    pub const fn move_score(&self, merge_score: u64, merges: u8, streak: u32) -> u64 {
+        if merges == 0 {
+            return merge_score;
+        }
+
+        let mut streak = streak as u64;
+        if self.streak_cap != 0 && streak > self.streak_cap as u64 {
+            streak = self.streak_cap as u64;
+        }
+        let bonus = self.combo_bonus as u64 * (merges as u64 - 1) + self.streak_bonus as u64 * streak;
+        merge_score.saturating_add(merge_score.saturating_mul(bonus) / BONUS_SCALE)
+    }
+}
+
+/// Streak after a move that merged `merges` times.
+pub const fn next_streak(streak: u32, merges: u8) -> u32 {
+    if merges == 0 {
+        0
+    } else {
+        streak.saturating_add(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BONUSES: ScoreBonuses = ScoreBonuses {
+        combo_bonus: 50,
+        streak_bonus: 10,
+        streak_cap: 3,
+    };
+
+    #[test]
+    fn plain_scoring_adds_nothing() {
+        assert_eq!(ScoreBonuses::NONE.move_score(64, 3, 12), 64);
+        assert!(!ScoreBonuses::NONE.is_enabled());
+    }
+
+    #[test]
+    fn combo_bonus_counts_merges_beyond_the_first() {
+        assert_eq!(BONUSES.move_score(100, 1, 0), 100);
+        assert_eq!(BONUSES.move_score(100, 2, 0), 150);
+        assert_eq!(BONUSES.move_score(100, 4, 0), 250);
+    }
+
+    #[test]
+    fn streak_bonus_stops_at_the_cap() {
+        assert_eq!(BONUSES.move_score(100, 1, 2), 120);
+        assert_eq!(BONUSES.move_score(100, 1, 3), 130);
+        assert_eq!(BONUSES.move_score(100, 1, 50), 130);
+        assert_eq!(BONUSES.move_score(100, 2, 3), 180);
+
+        let uncapped = ScoreBonuses { streak_cap: 0, ..BONUSES };
+        assert_eq!(uncapped.move_score(100, 1, 50), 600);
+    }
+
+    #[test]
+    fn moves_without_merge_score_nothing() {
+        assert_eq!(BONUSES.move_score(0, 0, 3), 0);
+    }
+
+    #[test]
+    fn large_scores_saturate() {
+        let max = ScoreBonuses {
+            combo_bonus: MAX_SCORE_BONUS,
+            streak_bonus: MAX_SCORE_BONUS,
+            streak_cap: 0,
+        };
+        assert_eq!(max.move_score(u64::MAX - 1, 8, u32::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn streak_resets_on_a_move_without_merge() {
+        assert_eq!(next_streak(0, 1), 1);
+        assert_eq!(next_streak(4, 2), 5);
+        assert_eq!(next_streak(4, 0), 0);
+        assert_eq!(next_streak(u32::MAX, 1), u32::MAX);
+    }
+
+    #[test]
+    fn bonuses_above_the_maximum_are_invalid() {
+        assert!(BONUSES.is_valid());
+        assert!(!ScoreBonuses { combo_bonus: MAX_SCORE_BONUS + 1, ..BONUSES }.is_valid());
+        assert!(!ScoreBonuses { streak_bonus: MAX_SCORE_BONUS + 1, ..BONUSES }.is_valid());
+    }
+}
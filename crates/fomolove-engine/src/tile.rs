@@ -0,0 +1,152 @@
+//! Cell encoding of the special tiles.
+//!
+//! A cell is one byte: the two high bits hold the [`TileKind`] and the six low
+//! bits its payload. Vanilla tiles have kind 0, so a vanilla board is exactly
+//! the plain exponent encoding.
+
+use crate::{tile_value, MAX_TILE_EXPONENT};
+
+const KIND_SHIFT: u8 = 6;
+const PAYLOAD_MASK: u8 = 0x3F;
+
+/// Largest number of moves a blocker can stay on the board.
+pub const MAX_BLOCKER_LIFETIME: u8 = PAYLOAD_MASK;
+
+/// Chances of a spawn are expressed out of this many draws.
+pub const SPECIAL_CHANCE_SCALE: u16 = 1000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TileKind {
+    /// Payload is the exponent, 0 for an empty cell.
+    Normal,
+    /// Payload is the exponent, the merge it takes part in scores double.
+    Multiplier,
+    /// Merges with any normal or multiplier tile, payload unused.
+    Wildcard,
+    /// Never moves nor merges, payload is the number of moves left.
+    Blocker,
+}
+
+pub const fn tile_kind(cell: u8) -> TileKind {
+    match cell >> KIND_SHIFT {
+        0 => TileKind::Normal,
+        1 => TileKind::Multiplier,
+        2 => TileKind::Wildcard,
+        _ => TileKind::Blocker,
+    }
+}
+
+/// Exponent of a normal or multiplier tile, 0 for empty cells, wildcards and
+/// blockers.
+pub const fn cell_exponent(cell: u8) -> u8 {
+    match tile_kind(cell) {
+        TileKind::Normal | TileKind::Multiplier => cell & PAYLOAD_MASK,
+        TileKind::Wildcard | TileKind::Blocker => 0,
+    }
+}
+
+pub const fn multiplier_tile(exponent: u8) -> u8 {
+    (1 << KIND_SHIFT) | (exponent & PAYLOAD_MASK)
+}
+
+pub const fn wildcard_tile() -> u8 {
+    2 << KIND_SHIFT
+}
+
+/// Blocker that vanishes after `lifetime` moves, in `1..=MAX_BLOCKER_LIFETIME`.
+pub const fn blocker_tile(lifetime: u8) -> u8 {
+    (3 << KIND_SHIFT) | (lifetime & PAYLOAD_MASK)
+}
+
+/// Moves left before a blocker vanishes, 0 for any other cell.
+pub const fn blocker_lifetime(cell: u8) -> u8 {
+    match tile_kind(cell) {
+        TileKind::Blocker => cell & PAYLOAD_MASK,
+        _ => 0,
+    }
+}
+
+/// Whether the cell slides with the move: any tile but a blocker.
+pub const fn is_movable(cell: u8) -> bool {
+    cell != 0 && !matches!(tile_kind(cell), TileKind::Blocker)
+}
+
+/// Tile and score produced by merging `a` into `b`, `None` when they do not
+/// merge. The merged tile is always a normal one.
+pub const fn merge_tiles(a: u8, b: u8) -> Option<(u8, u64)> {
+    if !is_movable(a) || !is_movable(b) {
+        return None;
+    }
+
+    let exponent = match (tile_kind(a), tile_kind(b)) {
+        (TileKind::Wildcard, TileKind::Wildcard) => return None,
+        (TileKind::Wildcard, _) => cell_exponent(b),
+        (_, TileKind::Wildcard) => cell_exponent(a),
+        _ => {
+            if cell_exponent(a) != cell_exponent(b) {
+                return None;
+            }
+            cell_exponent(a)
+        }
+    };
+    if exponent >= MAX_TILE_EXPONENT {
+        return None;
+    }
+
+    let new_tile = exponent + 1;
+    let mut score = tile_value(new_tile);
+    if matches!(tile_kind(a), TileKind::Multiplier) || matches!(tile_kind(b), TileKind::Multiplier) {
+        score *= 2;
+    }
+    Some((new_tile, score))
+}
+
+/// Chances, out of [`SPECIAL_CHANCE_SCALE`], that a spawned tile is special.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SpecialTiles {
+    pub blocker_chance: u16,
+    pub wildcard_chance: u16,
+    pub multiplier_chance: u16,
+    /// Moves a spawned blocker stays on the board
+    pub blocker_lifetime: u8,
+}
+
+impl SpecialTiles {
+    /// Vanilla 2048, every spawn is a 2 or a 4.
+    pub const NONE: SpecialTiles = SpecialTiles {
+        blocker_chance: 0,
+        wildcard_chance: 0,
+        multiplier_chance: 0,
+        blocker_lifetime: 0,
+    };
+
+    pub const fn is_enabled(&self) -> bool {
+        self.blocker_chance != 0 || self.wildcard_chance != 0 || self.multiplier_chance != 0
+    }
+
+    pub const fn is_valid(&self) -> bool {
+        let total = self.blocker_chance as u32 + self.wildcard_chance as u32 + self.multiplier_chance as u32;
+        let lifetime_ok = self.blocker_chance == 0
+            || (self.blocker_lifetime >= 1 && self.blocker_lifetime <= MAX_BLOCKER_LIFETIME);
+        total <= SPECIAL_CHANCE_SCALE as u32 && lifetime_ok
+    }
+
+    /// Cell spawned for a `roll` in `0..SPECIAL_CHANCE_SCALE` when the vanilla
+    /// tile would have been `exponent`.
+    pub const fn spawned_cell(&self, roll: u16, exponent: u8) -> u8 {
+        let roll = roll as u32;
+        let mut threshold = self.blocker_chance as u32;
+        if roll < threshold {
+            return blocker_tile(self.blocker_lifetime);
+        }
+        threshold += self.wildcard_chance as u32;
+        if roll < threshold {
+            return wildcard_tile();
+        }
+        threshold += self.multiplier_chance as u32;
+        if roll < threshold {
+            return multiplier_tile(exponent);
+        }
+        exponent
+    }
+}
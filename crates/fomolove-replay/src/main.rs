@@ -0,0 +1,94 @@
+//! Audits a game by replaying its move log with the program rules.
+//!
+//! ```text
+//! solana account <GAME> --output-file game.bin
+//! solana account <GAME_LOG> --output-file game_log.bin
+//! fomolove-replay <GAME> game.bin game_log.bin [spawns.txt]
+//! ```
+//!
+//! Daily challenge games spawn from their seed alone. SlotHashes and oracle
+//! games also need the randomness of their `SpawnRandomness` events, see
+//! [`spawns`]. The replayed board, score and top tile are printed, and the
+//! exit code is 1 when they differ from the account, 2 on invalid input.
+
+mod replay;
+mod spawns;
+
+use std::process::ExitCode;
+use std::str::FromStr;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::AccountDeserialize;
+use fomolove_engine::{blocker_lifetime, cell_exponent, tile_kind, tile_value, TileKind};
+use fomolove_sonic::states::{GameAccount, GameLogAccount};
+
+use replay::{differences, replay};
+use spawns::SpawnInputs;
+
+const USAGE: &str = "usage: fomolove-replay <GAME_ADDRESS> <GAME_ACCOUNT_FILE> <GAME_LOG_FILE> [SPAWN_RANDOMNESS_FILE]";
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Returns whether the replay matches the recorded game.
+fn run() -> Result<bool, String> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.len() < 3 || args.len() > 4 {
+        return Err(USAGE.to_string());
+    }
+
+    let game_key = Pubkey::from_str(&args[0]).map_err(|error| format!("invalid game address: {}", error))?;
+    let game: GameAccount = read_account(&args[1])?;
+    let log: GameLogAccount = read_account(&args[2])?;
+    let spawns = match args.get(3) {
+        Some(path) => {
+            let text = std::fs::read_to_string(path).map_err(|error| format!("{}: {}", path, error))?;
+            SpawnInputs::parse(&text).map_err(|error| format!("{}: {}", path, error))?
+        }
+        None => SpawnInputs::default(),
+    };
+
+    let replayed = replay(&game, &game_key, &log, &spawns).map_err(|error| error.to_string())?;
+
+    print_board(&replayed);
+    println!("score: {}", replayed.score);
+    println!("top tile: {}", replayed.top_tile);
+    println!("moves: {}", replayed.move_count);
+
+    let differences = differences(&game, &replayed);
+    for (field, recorded, replayed) in &differences {
+        eprintln!("{} differs: account has {}, replay has {}", field, recorded, replayed);
+    }
+    Ok(differences.is_empty())
+}
+
+/// Reads a raw account dump, as written by `solana account --output-file`.
+fn read_account<T: AccountDeserialize>(path: &str) -> Result<T, String> {
+    let data = std::fs::read(path).map_err(|error| format!("{}: {}", path, error))?;
+    T::try_deserialize(&mut data.as_slice()).map_err(|error| format!("{}: {}", path, error))
+}
+
+fn print_board(game: &GameAccount) {
+    for row in game.board.chunks(game.board_size as usize) {
+        let cells: Vec<String> = row.iter().map(|&cell| format!("{:>8}", cell_label(cell))).collect();
+        println!("{}", cells.join(""));
+    }
+}
+
+fn cell_label(cell: u8) -> String {
+    match tile_kind(cell) {
+        TileKind::Normal if cell == 0 => ".".to_string(),
+        TileKind::Normal => tile_value(cell_exponent(cell)).to_string(),
+        TileKind::Multiplier => format!("x2:{}", tile_value(cell_exponent(cell))),
+        TileKind::Wildcard => "wild".to_string(),
+        TileKind::Blocker => format!("block:{}", blocker_lifetime(cell)),
+    }
+}
@@ -0,0 +1,341 @@
+//! Re-executes a game with the program's own `GameAccount` rules.
+
+use std::fmt;
+
+use anchor_lang::prelude::Pubkey;
+use fomolove_engine::decode_moves;
+use fomolove_sonic::states::{GameAccount, GameLogAccount, GameStatus, RandomnessMode};
+
+use crate::spawns::SpawnInputs;
+
+#[derive(Debug)]
+pub enum ReplayError {
+    /// The log belongs to another game.
+    LogMismatch,
+    /// No randomness is known for the spawn that follows this move.
+    MissingRandomness(u32),
+    /// The move at this index left the board unchanged, the program refuses it.
+    MoveRejected(u32),
+    /// The move at this index came after the game had ended.
+    MoveAfterEnd(u32),
+    /// The program rules failed, e.g. a board size the engine does not know.
+    Program(String),
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::LogMismatch => write!(f, "the move log belongs to another game"),
+            ReplayError::MissingRandomness(move_count) => {
+                write!(f, "no spawn randomness for move {}", move_count)
+            }
+            ReplayError::MoveRejected(index) => {
+                write!(f, "move {} does not change the board", index + 1)
+            }
+            ReplayError::MoveAfterEnd(index) => {
+                write!(f, "move {} was played after the game ended", index + 1)
+            }
+            ReplayError::Program(error) => write!(f, "program error: {}", error),
+        }
+    }
+}
+
+impl From<anchor_lang::error::Error> for ReplayError {
+    fn from(error: anchor_lang::error::Error) -> Self {
+        ReplayError::Program(error.to_string())
+    }
+}
+
+/// Replays every logged move from a fresh board with the rules recorded on
+/// `recorded` and returns the resulting game.
+pub fn replay(
+    recorded: &GameAccount,
+    game_key: &Pubkey,
+    log: &GameLogAccount,
+    spawns: &SpawnInputs,
+) -> Result<GameAccount, ReplayError> {
+    if log.game != *game_key {
+        return Err(ReplayError::LogMismatch);
+    }
+
+    // Same state as `register_game` leaves before the opening tiles
+    let mut game = recorded.clone();
+    let cell_count = recorded.board_size as usize * recorded.board_size as usize;
+    game.board = vec![0; cell_count];
+    game.score = 0;
+    game.top_tile = 2;
+    game.move_count = 0;
+    game.streak = 0;
+    game.best_streak = 0;
+    game.best_combo = 0;
+    game.pending_spawn = false;
+    game.status = GameStatus::Active;
+
+    let randomness = randomness_for(&game, spawns)?;
+    let mut entropy = game.spawn_entropy(game_key, &randomness);
+    game.add_new_tile(&mut entropy)?;
+    game.add_new_tile(&mut entropy)?;
+
+    let move_count = log.move_count;
+    for (index, direction) in decode_moves(&log.moves, move_count as usize).enumerate() {
+        let index = index as u32;
+        if game.status != GameStatus::Active {
+            return Err(ReplayError::MoveAfterEnd(index));
+        }
+        if game.apply_move(direction.into())?.is_none() {
+            return Err(ReplayError::MoveRejected(index));
+        }
+        game.move_count += 1;
+
+        // The oracle may not have revealed the spawn of the last move yet
+        let last_move = index + 1 == move_count;
+        if last_move && recorded.randomness_mode == RandomnessMode::Oracle && recorded.pending_spawn {
+            game.pending_spawn = true;
+        } else {
+            let randomness = randomness_for(&game, spawns)?;
+            let mut entropy = game.spawn_entropy(game_key, &randomness);
+            game.add_new_tile(&mut entropy)?;
+        }
+
+        game.finish_if_over()?;
+    }
+
+    Ok(game)
+}
+
+fn randomness_for(game: &GameAccount, spawns: &SpawnInputs) -> Result<[u8; 32], ReplayError> {
+    match game.randomness_mode {
+        RandomnessMode::Challenge => Ok(game.seed),
+        RandomnessMode::SlotHashes | RandomnessMode::Oracle => spawns
+            .randomness_for(game.move_count)
+            .ok_or(ReplayError::MissingRandomness(game.move_count)),
+    }
+}
+
+/// Fields of the recorded game that differ from the replay, as
+/// `(field, recorded, replayed)`.
+pub fn differences(recorded: &GameAccount, replayed: &GameAccount) -> Vec<(&'static str, String, String)> {
+    let mut differences = Vec::new();
+    let mut compare = |field, recorded: String, replayed: String| {
+        if recorded != replayed {
+            differences.push((field, recorded, replayed));
+        }
+    };
+
+    compare("board", format!("{:?}", recorded.board), format!("{:?}", replayed.board));
+    compare("score", recorded.score.to_string(), replayed.score.to_string());
+    compare("top_tile", recorded.top_tile.to_string(), replayed.top_tile.to_string());
+    compare("move_count", recorded.move_count.to_string(), replayed.move_count.to_string());
+    compare("streak", recorded.streak.to_string(), replayed.streak.to_string());
+    compare("best_streak", recorded.best_streak.to_string(), replayed.best_streak.to_string());
+    compare("best_combo", recorded.best_combo.to_string(), replayed.best_combo.to_string());
+    compare(
+        "pending_spawn",
+        recorded.pending_spawn.to_string(),
+        replayed.pending_spawn.to_string(),
+    );
+
+    // Submitted and expired games were Active or Over when they were closed
+    if matches!(recorded.status, GameStatus::Active | GameStatus::Over) {
+        compare(
+            "status",
+            format!("{:?}", recorded.status),
+            format!("{:?}", replayed.status),
+        );
+    }
+
+    differences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fomolove_sonic::states::{Direction, GameMode, ScoringRules, SpecialTileRules};
+
+    const MOVES: [Direction; 4] = [Direction::Up, Direction::Left, Direction::Down, Direction::Right];
+
+    fn hex(randomness: &[u8; 32]) -> String {
+        randomness.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    /// A game as `register_game` leaves it, opening tiles drawn from `randomness`.
+    fn new_game(game_key: &Pubkey, randomness_mode: RandomnessMode, randomness: [u8; 32]) -> GameAccount {
+        let mut game = GameAccount {
+            nft_mint: Pubkey::new_from_array([1; 32]),
+            owner: Pubkey::new_from_array([2; 32]),
+            season_id: 1,
+            board_size: 4,
+            board: vec![0; 16],
+            score: 0,
+            top_tile: 2,
+            seed: [7; 32],
+            move_count: 0,
+            randomness_mode,
+            pending_spawn: false,
+            status: GameStatus::Active,
+            mode: GameMode::Classic,
+            ends_at: 0,
+            paused_time: 0,
+            move_limit: 0,
+            target_tile: 0,
+            special_tiles: SpecialTileRules {
+                blocker_chance: 20,
+                wildcard_chance: 20,
+                multiplier_chance: 20,
+                blocker_lifetime: 5,
+            },
+            daily_challenge: Pubkey::default(),
+            scoring: ScoringRules {
+                combo_bonus: 10,
+                streak_bonus: 5,
+                streak_cap: 4,
+            },
+            streak: 0,
+            best_streak: 0,
+            best_combo: 0,
+        };
+        if randomness_mode == RandomnessMode::Challenge {
+            game.seed = randomness;
+            game.daily_challenge = Pubkey::new_from_array([3; 32]);
+        }
+
+        let mut entropy = game.spawn_entropy(game_key, &randomness);
+        game.add_new_tile(&mut entropy).unwrap();
+        game.add_new_tile(&mut entropy).unwrap();
+        game
+    }
+
+    /// Plays up to `batches` batches of up to `batch_len` moves the way
+    /// `play_moves` does, one fresh randomness per batch, and returns the log
+    /// with the `SpawnRandomness` records the program would have emitted.
+    fn play(
+        game: &mut GameAccount,
+        game_key: &Pubkey,
+        opening: [u8; 32],
+        batches: u8,
+        batch_len: usize,
+    ) -> (GameLogAccount, String) {
+        let mut log = GameLogAccount {
+            bump: 255,
+            game: *game_key,
+            move_count: 0,
+            moves: Vec::new(),
+        };
+        let mut records = format!("0 {}\n", hex(&opening));
+
+        let mut next = 0;
+        for batch in 0..batches {
+            let randomness = match game.randomness_mode {
+                RandomnessMode::Challenge => game.seed,
+                _ => [batch.wrapping_mul(31).wrapping_add(1); 32],
+            };
+            if game.randomness_mode != RandomnessMode::Challenge {
+                records.push_str(&format!("{} {}\n", game.move_count + 1, hex(&randomness)));
+            }
+
+            let mut applied = 0;
+            while applied < batch_len && game.status == GameStatus::Active {
+                // The first direction that changes the board, as a player would
+                let direction = (0..MOVES.len())
+                    .map(|offset| MOVES[(next + offset) % MOVES.len()])
+                    .find(|&direction| game.clone().apply_move(direction).unwrap().is_some())
+                    .unwrap();
+                next += 1;
+
+                game.apply_move(direction).unwrap();
+                game.move_count += 1;
+                log.record(direction);
+                applied += 1;
+
+                let mut entropy = game.spawn_entropy(game_key, &randomness);
+                game.add_new_tile(&mut entropy).unwrap();
+                game.finish_if_over().unwrap();
+            }
+        }
+
+        (log, records)
+    }
+
+    #[test]
+    fn challenge_game_replays_without_differences() {
+        let game_key = Pubkey::new_from_array([4; 32]);
+        let mut game = new_game(&game_key, RandomnessMode::Challenge, [3; 32]);
+        let (log, _) = play(&mut game, &game_key, [3; 32], 1, 200);
+        assert_eq!(log.move_count, game.move_count);
+        assert!(game.move_count > 50);
+
+        let replayed = replay(&game, &game_key, &log, &SpawnInputs::default()).unwrap();
+        assert!(differences(&game, &replayed).is_empty());
+    }
+
+    #[test]
+    fn slot_hashes_game_replays_with_its_spawn_records() {
+        let game_key = Pubkey::new_from_array([4; 32]);
+        let mut game = new_game(&game_key, RandomnessMode::SlotHashes, [9; 32]);
+        let (log, records) = play(&mut game, &game_key, [9; 32], 12, 8);
+        let spawns = SpawnInputs::parse(&records).unwrap();
+
+        let replayed = replay(&game, &game_key, &log, &spawns).unwrap();
+        assert!(differences(&game, &replayed).is_empty());
+    }
+
+    #[test]
+    fn oracle_game_replays_its_pending_spawn() {
+        let game_key = Pubkey::new_from_array([4; 32]);
+        let mut game = new_game(&game_key, RandomnessMode::Oracle, [5; 32]);
+        let (mut log, records) = play(&mut game, &game_key, [5; 32], 10, 1);
+
+        // One more move whose spawn the oracle has not revealed yet
+        let direction = MOVES
+            .into_iter()
+            .find(|&direction| game.clone().apply_move(direction).unwrap().is_some())
+            .unwrap();
+        game.apply_move(direction).unwrap();
+        game.move_count += 1;
+        game.pending_spawn = true;
+        log.record(direction);
+
+        let spawns = SpawnInputs::parse(&records).unwrap();
+        let replayed = replay(&game, &game_key, &log, &spawns).unwrap();
+        assert!(replayed.pending_spawn);
+        assert!(differences(&game, &replayed).is_empty());
+    }
+
+    #[test]
+    fn wrong_seed_is_reported() {
+        let game_key = Pubkey::new_from_array([4; 32]);
+        let mut game = new_game(&game_key, RandomnessMode::Challenge, [3; 32]);
+        let (log, _) = play(&mut game, &game_key, [3; 32], 1, 40);
+
+        let mut claimed = game.clone();
+        claimed.seed = [4; 32];
+        let replayed = replay(&claimed, &game_key, &log, &SpawnInputs::default()).unwrap();
+        let fields: Vec<_> = differences(&game, &replayed).into_iter().map(|(field, _, _)| field).collect();
+        assert!(fields.contains(&"board"));
+        assert!(fields.contains(&"score"));
+    }
+
+    #[test]
+    fn tampered_log_is_reported() {
+        let game_key = Pubkey::new_from_array([4; 32]);
+        let mut game = new_game(&game_key, RandomnessMode::Challenge, [3; 32]);
+        let (mut log, _) = play(&mut game, &game_key, [3; 32], 1, 40);
+
+        // Rotate the direction of the 10th move, 2 bits per move
+        log.moves[2] ^= 0b01 << 2;
+        let replayed = replay(&game, &game_key, &log, &SpawnInputs::default()).unwrap();
+        let fields: Vec<_> = differences(&game, &replayed).into_iter().map(|(field, _, _)| field).collect();
+        assert!(fields.contains(&"board"));
+        assert!(fields.contains(&"score"));
+    }
+
+    #[test]
+    fn log_of_another_game_is_refused() {
+        let game_key = Pubkey::new_from_array([4; 32]);
+        let mut game = new_game(&game_key, RandomnessMode::Challenge, [3; 32]);
+        let (log, _) = play(&mut game, &game_key, [3; 32], 1, 10);
+
+        let result = replay(&game, &Pubkey::new_from_array([5; 32]), &log, &SpawnInputs::default());
+        assert!(matches!(result, Err(ReplayError::LogMismatch)));
+    }
+}
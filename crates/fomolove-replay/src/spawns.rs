@@ -0,0 +1,84 @@
+//! Spawn randomness of SlotHashes and oracle games, as emitted by the
+//! program in `SpawnRandomness` events.
+//!
+//! One record per line, `<first_move> <randomness as 64 hex chars>`. Blank
+//! lines and lines starting with `#` are skipped.
+
+use std::fmt;
+
+#[derive(Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: &'static str,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Randomness records sorted by the first move they apply to.
+#[derive(Debug, Default)]
+pub struct SpawnInputs {
+    records: Vec<(u32, [u8; 32])>,
+}
+
+impl SpawnInputs {
+    pub fn parse(text: &str) -> Result<Self, ParseError> {
+        let mut records = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let error = |message| ParseError {
+                line: index + 1,
+                message,
+            };
+            let mut fields = line.split_whitespace();
+            let first_move = fields
+                .next()
+                .and_then(|field| field.parse::<u32>().ok())
+                .ok_or_else(|| error("expected the first move number"))?;
+            let randomness = fields
+                .next()
+                .and_then(decode_hex)
+                .ok_or_else(|| error("expected 32 bytes of hex randomness"))?;
+            if fields.next().is_some() {
+                return Err(error("unexpected trailing field"));
+            }
+
+            records.push((first_move, randomness));
+        }
+
+        // A later record of the same move wins, as it would on-chain
+        records.sort_by_key(|&(first_move, _)| first_move);
+        Ok(Self { records })
+    }
+
+    /// Randomness of the spawn that follows move `move_count`, 0 being the
+    /// opening tiles.
+    pub fn randomness_for(&self, move_count: u32) -> Option<[u8; 32]> {
+        self.records
+            .iter()
+            .rev()
+            .find(|&&(first_move, _)| first_move <= move_count)
+            .map(|&(_, randomness)| randomness)
+    }
+}
+
+fn decode_hex(text: &str) -> Option<[u8; 32]> {
+    let text = text.strip_prefix("0x").unwrap_or(text);
+    if text.len() != 64 {
+        return None;
+    }
+
+    let mut bytes = [0u8; 32];
+    for (byte, pair) in bytes.iter_mut().zip(text.as_bytes().chunks(2)) {
+        let pair = std::str::from_utf8(pair).ok()?;
+        *byte = u8::from_str_radix(pair, 16).ok()?;
+    }
+    Some(bytes)
+}
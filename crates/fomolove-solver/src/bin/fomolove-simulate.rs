@@ -0,0 +1,200 @@
+//! Simulates games under a season ruleset and prints the score, top tile
+//! and move distributions.
+//!
+//! ```text
+//! fomolove-simulate [--games 1000] [--board-size 4] [--mode classic|moves:N|sprint:TILE]
+//!     [--blockers N] [--wildcards N] [--multipliers N] [--blocker-lifetime N]
+//!     [--combo-bonus N] [--streak-bonus N] [--streak-cap N]
+//!     [--strategy expectimax|random] [--depth 2] [--seed 0] [--threads N]
+//! ```
+//!
+//! Special tile chances are per mille and score bonuses in percent, as in
+//! `start_season`.
+
+use std::process::ExitCode;
+
+use fomolove_engine::{is_valid_board_size, tile_exponent, tile_value, ScoreBonuses, SpecialTiles};
+use fomolove_solver::{percentile, simulate, GameResult, ModeLimit, Ruleset, Strategy};
+
+struct Options {
+    games: usize,
+    ruleset: Ruleset,
+    strategy: Strategy,
+    seed: u64,
+    threads: usize,
+}
+
+fn main() -> ExitCode {
+    let options = match parse_options(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("{}", error);
+            return ExitCode::from(2);
+        }
+    };
+
+    let results = simulate(
+        &options.ruleset,
+        options.strategy,
+        options.games,
+        options.seed,
+        options.threads,
+    );
+    report(&options, &results);
+    ExitCode::SUCCESS
+}
+
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        games: 1000,
+        ruleset: Ruleset {
+            board_size: 4,
+            special_tiles: SpecialTiles::NONE,
+            score_bonuses: ScoreBonuses::NONE,
+            limit: ModeLimit::None,
+        },
+        strategy: Strategy::Expectimax(2),
+        seed: 0,
+        threads: std::thread::available_parallelism().map_or(1, |threads| threads.get()),
+    };
+    let mut random = false;
+    let mut depth = 2;
+
+    while let Some(flag) = args.next() {
+        let value = args.next().ok_or_else(|| format!("{} needs a value", flag))?;
+        let number = || value.parse::<u64>().map_err(|_| format!("{}: invalid number {}", flag, value));
+        let special = &mut options.ruleset.special_tiles;
+        let bonuses = &mut options.ruleset.score_bonuses;
+
+        match flag.as_str() {
+            "--games" => options.games = number()? as usize,
+            "--board-size" => options.ruleset.board_size = number()? as usize,
+            "--mode" => options.ruleset.limit = parse_mode(&value)?,
+            "--blockers" => special.blocker_chance = number()? as u16,
+            "--wildcards" => special.wildcard_chance = number()? as u16,
+            "--multipliers" => special.multiplier_chance = number()? as u16,
+            "--blocker-lifetime" => special.blocker_lifetime = number()? as u8,
+            "--combo-bonus" => bonuses.combo_bonus = number()? as u16,
+            "--streak-bonus" => bonuses.streak_bonus = number()? as u16,
+            "--streak-cap" => bonuses.streak_cap = number()? as u8,
+            "--strategy" => {
+                random = match value.as_str() {
+                    "expectimax" => false,
+                    "random" => true,
+                    _ => return Err(format!("unknown strategy {}", value)),
+                }
+            }
+            "--depth" => depth = number()? as u8,
+            "--seed" => options.seed = number()?,
+            "--threads" => options.threads = number()? as usize,
+            _ => return Err(format!("unknown option {}", flag)),
+        }
+    }
+
+    if !is_valid_board_size(options.ruleset.board_size) {
+        return Err("the board size must be between 3 and 8".to_string());
+    }
+    if !options.ruleset.special_tiles.is_valid() {
+        return Err("the special tile chances are invalid".to_string());
+    }
+    if !options.ruleset.score_bonuses.is_valid() {
+        return Err("the score bonuses are too large".to_string());
+    }
+    options.strategy = if random {
+        Strategy::Random
+    } else {
+        Strategy::Expectimax(depth)
+    };
+    Ok(options)
+}
+
+fn parse_mode(value: &str) -> Result<ModeLimit, String> {
+    let invalid = || format!("invalid mode {}, expected classic, moves:N or sprint:TILE", value);
+    match value.split_once(':') {
+        None if value == "classic" => Ok(ModeLimit::None),
+        Some(("moves", moves)) => moves.parse().map(ModeLimit::Moves).map_err(|_| invalid()),
+        Some(("sprint", tile)) => {
+            let tile: u64 = tile.parse().map_err(|_| invalid())?;
+            if tile <= 4 || !tile.is_power_of_two() {
+                return Err(invalid());
+            }
+            Ok(ModeLimit::TargetTile(tile_exponent(tile)))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+fn report(options: &Options, results: &[GameResult]) {
+    let ruleset = &options.ruleset;
+    let size = ruleset.board_size;
+    println!(
+        "{} games, {}x{} board, {:?}, {:?}, seed {}",
+        results.len(),
+        size,
+        size,
+        ruleset.limit,
+        options.strategy,
+        options.seed
+    );
+    if ruleset.special_tiles.is_enabled() {
+        println!("special tiles: {:?}", ruleset.special_tiles);
+    }
+    if ruleset.score_bonuses.is_enabled() {
+        println!("score bonuses: {:?}", ruleset.score_bonuses);
+    }
+    if results.is_empty() {
+        return;
+    }
+
+    let mut scores: Vec<u64> = results.iter().map(|result| result.score).collect();
+    scores.sort_unstable();
+    let mut moves: Vec<u32> = results.iter().map(|result| result.moves).collect();
+    moves.sort_unstable();
+
+    let mean_score = scores.iter().sum::<u64>() as f64 / scores.len() as f64;
+    println!("\nscore    mean {:.0}", mean_score);
+    print_percentiles(&scores);
+    println!("\nmoves");
+    print_percentiles(&moves);
+
+    // Share of games whose top tile reached at least each value
+    println!("\ntop tile   games   reached");
+    let mut top_tiles: Vec<u8> = results.iter().map(|result| result.top_tile).collect();
+    top_tiles.sort_unstable();
+    let mut reached = top_tiles.len();
+    for tile in top_tiles[0]..=top_tiles[top_tiles.len() - 1] {
+        let games = top_tiles.iter().filter(|&&top_tile| top_tile == tile).count();
+        println!(
+            "{:>8} {:>7} {:>8.1}%",
+            tile_value(tile),
+            games,
+            reached as f64 * 100.0 / results.len() as f64
+        );
+        reached -= games;
+    }
+
+    if let ModeLimit::TargetTile(target) = ruleset.limit {
+        let mut sprint_moves: Vec<u32> = results
+            .iter()
+            .filter(|result| result.reached_target)
+            .map(|result| result.moves)
+            .collect();
+        sprint_moves.sort_unstable();
+        println!(
+            "\nsprint to {}: {:.1}% reached",
+            tile_value(target),
+            sprint_moves.len() as f64 * 100.0 / results.len() as f64
+        );
+        if !sprint_moves.is_empty() {
+            print_percentiles(&sprint_moves);
+        }
+    }
+}
+
+fn print_percentiles<T: Copy + std::fmt::Display>(sorted: &[T]) {
+    for rank in [10.0, 50.0, 90.0, 99.0, 100.0] {
+        if let Some(value) = percentile(sorted, rank) {
+            println!("    p{:<4} {}", rank, value);
+        }
+    }
+}
@@ -0,0 +1,198 @@
+//! Depth limited expectimax over the engine moves and spawns.
+
+use std::collections::HashMap;
+
+use fomolove_engine::{Board, Direction, SpecialTiles, SPECIAL_CHANCE_SCALE};
+
+use crate::evaluate;
+
+/// Chance of a 4 instead of a 2, as drawn by the program and `SeededEntropy`.
+pub const FOUR_CHANCE: f64 = 0.1;
+
+/// Chance nodes less likely than this are evaluated statically.
+const PROBABILITY_CUTOFF: f64 = 1e-4;
+
+pub struct Expectimax {
+    /// Player moves searched ahead, 1 looks at every spawn of the next move.
+    pub depth: u8,
+    /// Cells a spawn can produce and their chances.
+    outcomes: Vec<(u8, f64)>,
+    cache: HashMap<(Board, u8), f64>,
+}
+
+impl Expectimax {
+    pub fn new(depth: u8, special_tiles: &SpecialTiles) -> Self {
+        Self {
+            depth: depth.max(1),
+            outcomes: spawn_outcomes(special_tiles),
+            cache: HashMap::new(),
+        }
+    }
+
+    /// Move with the best expected value, `None` when the game is over.
+    pub fn best_move(&mut self, board: &Board) -> Option<Direction> {
+        self.cache.clear();
+
+        let mut best: Option<(Direction, f64)> = None;
+        for direction in Direction::ALL {
+            let mut next = *board;
+            let outcome = next.apply_move(direction);
+            if !outcome.moved {
+                continue;
+            }
+
+            let value = outcome.score as f64 + self.chance_node(&next, self.depth - 1, 1.0);
+            if best.map_or(true, |(_, best_value)| value > best_value) {
+                best = Some((direction, value));
+            }
+        }
+        best.map(|(direction, _)| direction)
+    }
+
+    /// Best value over the moves of `board`, 0 when none is left.
+    fn max_node(&mut self, board: &Board, depth: u8, probability: f64) -> f64 {
+        let mut best = 0.0;
+        for direction in Direction::ALL {
+            let mut next = *board;
+            let outcome = next.apply_move(direction);
+            if outcome.moved {
+                let value = outcome.score as f64 + self.chance_node(&next, depth - 1, probability);
+                if value > best {
+                    best = value;
+                }
+            }
+        }
+        best
+    }
+
+    /// Expected value over every spawn on `board`, followed by `depth` more
+    /// player moves.
+    fn chance_node(&mut self, board: &Board, depth: u8, probability: f64) -> f64 {
+        let empty_cells = board.empty_cells();
+        if empty_cells.is_empty() {
+            return evaluate(board);
+        }
+        if let Some(&value) = self.cache.get(&(*board, depth)) {
+            return value;
+        }
+
+        let cell_chance = 1.0 / empty_cells.len() as f64;
+        let mut value = 0.0;
+        for index in 0..empty_cells.len() {
+            let Some((row, col)) = empty_cells.get(index) else {
+                continue;
+            };
+            for outcome in 0..self.outcomes.len() {
+                let (cell, chance) = self.outcomes[outcome];
+                let chance = chance * cell_chance;
+
+                let mut next = *board;
+                next.set(row, col, cell);
+                value += chance
+                    * if depth == 0 || probability * chance < PROBABILITY_CUTOFF {
+                        evaluate(&next)
+                    } else {
+                        self.max_node(&next, depth, probability * chance)
+                    };
+            }
+        }
+
+        self.cache.insert((*board, depth), value);
+        value
+    }
+}
+
+/// Every cell a spawn can produce under `special_tiles`, with its chance.
+/// Built from `SpecialTiles::spawned_cell` so it follows the engine exactly.
+pub fn spawn_outcomes(special_tiles: &SpecialTiles) -> Vec<(u8, f64)> {
+    let mut outcomes: Vec<(u8, f64)> = Vec::new();
+    for (exponent, chance) in [(1, 1.0 - FOUR_CHANCE), (2, FOUR_CHANCE)] {
+        if !special_tiles.is_enabled() {
+            outcomes.push((exponent, chance));
+            continue;
+        }
+
+        let roll_chance = chance / SPECIAL_CHANCE_SCALE as f64;
+        for roll in 0..SPECIAL_CHANCE_SCALE {
+            let cell = special_tiles.spawned_cell(roll, exponent);
+            match outcomes.iter_mut().find(|(known, _)| *known == cell) {
+                Some((_, total)) => *total += roll_chance,
+                None => outcomes.push((cell, roll_chance)),
+            }
+        }
+    }
+    outcomes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fomolove_engine::SeededEntropy;
+
+    const SPECIAL_TILES: SpecialTiles = SpecialTiles {
+        blocker_chance: 30,
+        wildcard_chance: 20,
+        multiplier_chance: 50,
+        blocker_lifetime: 6,
+    };
+
+    #[test]
+    fn spawn_outcomes_sum_to_one() {
+        assert_eq!(
+            spawn_outcomes(&SpecialTiles::NONE),
+            vec![(1, 1.0 - FOUR_CHANCE), (2, FOUR_CHANCE)]
+        );
+
+        for special_tiles in [SpecialTiles::NONE, SPECIAL_TILES] {
+            let total: f64 = spawn_outcomes(&special_tiles).iter().map(|&(_, chance)| chance).sum();
+            assert!((total - 1.0).abs() < 1e-9, "{:?} sums to {}", special_tiles, total);
+        }
+    }
+
+    #[test]
+    fn spawn_outcomes_follow_the_special_chances() {
+        let outcomes = spawn_outcomes(&SPECIAL_TILES);
+        let chance_of = |cell| {
+            outcomes
+                .iter()
+                .filter(|&&(known, _)| known == cell)
+                .map(|&(_, chance)| chance)
+                .sum::<f64>()
+        };
+
+        let special = (SPECIAL_TILES.blocker_chance + SPECIAL_TILES.wildcard_chance + SPECIAL_TILES.multiplier_chance)
+            as f64
+            / SPECIAL_CHANCE_SCALE as f64;
+        let plain = chance_of(1) + chance_of(2);
+        assert!((plain - (1.0 - special)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn best_move_always_changes_the_board() {
+        for special_tiles in [SpecialTiles::NONE, SPECIAL_TILES] {
+            let mut solver = Expectimax::new(1, &special_tiles);
+            let mut spawns = SeededEntropy::new(11);
+            let mut board = Board::new(4);
+            board.spawn_tile(&mut spawns, &special_tiles);
+            board.spawn_tile(&mut spawns, &special_tiles);
+
+            // Plays to the end, where few moves are left to pick from
+            loop {
+                let Some(direction) = solver.best_move(&board) else {
+                    assert!(board.is_game_over());
+                    break;
+                };
+                assert!(board.apply_move(direction).moved, "{:?} is a no-op", direction);
+                board.spawn_tile(&mut spawns, &special_tiles);
+            }
+        }
+    }
+
+    #[test]
+    fn best_move_is_none_once_the_game_is_over() {
+        let cells: Vec<u8> = (0..16).map(|index| 1 + ((index / 4 + index % 4) % 2) as u8).collect();
+        let board = Board::from_cells(4, &cells).unwrap();
+        assert!(board.is_game_over());
+        assert_eq!(Expectimax::new(3, &SpecialTiles::NONE).best_move(&board), None);
+    }
+}
@@ -0,0 +1,78 @@
+//! Static board evaluation, summed over every row and column.
+//!
+//! Rewards empty cells and pending merges, penalizes lines that are not
+//! monotonic and large tiles spread over the board. Special tiles count as
+//! exponent 0: blockers and wildcards are neither empty nor ranked.
+
+use fomolove_engine::{cell_exponent, Board, MAX_BOARD_SIZE};
+
+const LOST_PENALTY: f64 = 200_000.0;
+const MONOTONICITY_POWER: f64 = 4.0;
+const MONOTONICITY_WEIGHT: f64 = 47.0;
+const SUM_POWER: f64 = 3.5;
+const SUM_WEIGHT: f64 = 11.0;
+const MERGES_WEIGHT: f64 = 700.0;
+const EMPTY_WEIGHT: f64 = 270.0;
+
+pub fn evaluate(board: &Board) -> f64 {
+    let size = board.size();
+    let mut total = 0.0;
+    let mut line = [0u8; MAX_BOARD_SIZE];
+
+    for index in 0..size {
+        for (offset, cell) in line.iter_mut().enumerate().take(size) {
+            *cell = board.get(index, offset);
+        }
+        total += line_score(&line[..size]);
+
+        for (offset, cell) in line.iter_mut().enumerate().take(size) {
+            *cell = board.get(offset, index);
+        }
+        total += line_score(&line[..size]);
+    }
+    total
+}
+
+fn line_score(line: &[u8]) -> f64 {
+    let mut sum = 0.0;
+    let mut empty = 0;
+    let mut merges = 0;
+    let mut previous = 0;
+    let mut counter = 0;
+
+    for &cell in line {
+        let rank = cell_exponent(cell);
+        sum += (rank as f64).powf(SUM_POWER);
+        if cell == 0 {
+            empty += 1;
+            continue;
+        }
+
+        if rank != 0 && rank == previous {
+            counter += 1;
+        } else if counter > 0 {
+            merges += 1 + counter;
+            counter = 0;
+        }
+        previous = rank;
+    }
+    if counter > 0 {
+        merges += 1 + counter;
+    }
+
+    let mut monotonicity_left = 0.0;
+    let mut monotonicity_right = 0.0;
+    for pair in line.windows(2) {
+        let a = (cell_exponent(pair[0]) as f64).powf(MONOTONICITY_POWER);
+        let b = (cell_exponent(pair[1]) as f64).powf(MONOTONICITY_POWER);
+        if a > b {
+            monotonicity_left += a - b;
+        } else {
+            monotonicity_right += b - a;
+        }
+    }
+
+    LOST_PENALTY + EMPTY_WEIGHT * empty as f64 + MERGES_WEIGHT * merges as f64
+        - MONOTONICITY_WEIGHT * monotonicity_left.min(monotonicity_right)
+        - SUM_WEIGHT * sum
+}
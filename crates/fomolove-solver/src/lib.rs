@@ -0,0 +1,15 @@
+//! Expectimax 2048 player and self-play simulation on top of
+//! `fomolove-engine`, the rules `GameAccount` plays by.
+//!
+//! The `fomolove-simulate` binary plays thousands of games under a season
+//! ruleset and reports the score and top tile distributions, to pick
+//! leaderboard thresholds, spawn chances and mode limits before a season.
+
+pub mod heuristic;
+pub use heuristic::*;
+
+pub mod expectimax;
+pub use expectimax::*;
+
+pub mod simulation;
+pub use simulation::*;
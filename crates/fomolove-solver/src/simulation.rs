@@ -0,0 +1,213 @@
+//! Self-play of whole games under a season ruleset.
+
+use std::thread;
+
+use fomolove_engine::{next_streak, Board, Direction, ScoreBonuses, SeededEntropy, SpecialTiles};
+
+use crate::Expectimax;
+
+/// End condition of the game mode being simulated. TimeAttack has no
+/// simulated clock, approximate it with a move budget.
+#[derive(Clone, Copy, Debug)]
+pub enum ModeLimit {
+    /// Classic, plays until no move is left.
+    None,
+    /// MoveLimited, ends after this many moves.
+    Moves(u32),
+    /// Sprint, ends once a tile of this exponent is reached.
+    TargetTile(u8),
+}
+
+/// The parts of a season that shape its games.
+#[derive(Clone, Copy, Debug)]
+pub struct Ruleset {
+    pub board_size: usize,
+    pub special_tiles: SpecialTiles,
+    /// Applied to the game score, the search itself ranks moves by merge score
+    pub score_bonuses: ScoreBonuses,
+    pub limit: ModeLimit,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Strategy {
+    /// Uniform over the moves that change the board.
+    Random,
+    /// [`Expectimax`] searching this many moves ahead.
+    Expectimax(u8),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GameResult {
+    pub score: u64,
+    /// Exponent of the largest tile reached.
+    pub top_tile: u8,
+    pub moves: u32,
+    /// Whether a Sprint game reached its target tile.
+    pub reached_target: bool,
+}
+
+/// Plays one game, spawns drawn from `SeededEntropy::new(seed)`.
+pub fn play_game(ruleset: &Ruleset, strategy: Strategy, seed: u64) -> GameResult {
+    let mut spawns = SeededEntropy::new(seed);
+    let mut choices = SeededEntropy::new(!seed);
+    let mut solver = match strategy {
+        Strategy::Expectimax(depth) => Some(Expectimax::new(depth, &ruleset.special_tiles)),
+        Strategy::Random => None,
+    };
+
+    let mut board = Board::new(ruleset.board_size);
+    board.spawn_tile(&mut spawns, &ruleset.special_tiles);
+    board.spawn_tile(&mut spawns, &ruleset.special_tiles);
+
+    let mut result = GameResult {
+        score: 0,
+        top_tile: board.max_tile(),
+        moves: 0,
+        reached_target: false,
+    };
+    let mut streak = 0;
+
+    loop {
+        match ruleset.limit {
+            ModeLimit::Moves(limit) if result.moves >= limit => break,
+            ModeLimit::TargetTile(target) if result.top_tile >= target => {
+                result.reached_target = true;
+                break;
+            }
+            _ => {}
+        }
+
+        let direction = match solver.as_mut() {
+            Some(solver) => solver.best_move(&board),
+            None => random_move(&board, &mut choices),
+        };
+        let Some(direction) = direction else {
+            break;
+        };
+
+        let outcome = board.apply_move(direction);
+        result.score += ruleset.score_bonuses.move_score(outcome.score, outcome.merges, streak);
+        streak = next_streak(streak, outcome.merges);
+        result.moves += 1;
+
+        board.spawn_tile(&mut spawns, &ruleset.special_tiles);
+        result.top_tile = result.top_tile.max(board.max_tile());
+    }
+
+    result
+}
+
+fn random_move(board: &Board, choices: &mut SeededEntropy) -> Option<Direction> {
+    let mut moves = [Direction::Up; 4];
+    let mut count = 0;
+    for direction in Direction::ALL {
+        let mut next = *board;
+        if next.apply_move(direction).moved {
+            moves[count] = direction;
+            count += 1;
+        }
+    }
+
+    if count == 0 {
+        return None;
+    }
+    Some(moves[(choices.next_u64() % count as u64) as usize])
+}
+
+/// Plays `games` games over `threads` threads, game `i` seeded with
+/// `seed + i`. Results are in game order, so a run is reproducible.
+pub fn simulate(ruleset: &Ruleset, strategy: Strategy, games: usize, seed: u64, threads: usize) -> Vec<GameResult> {
+    let threads = threads.clamp(1, games.max(1));
+
+    let chunks: Vec<Vec<(usize, GameResult)>> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..threads)
+            .map(|worker| {
+                scope.spawn(move || {
+                    (worker..games)
+                        .step_by(threads)
+                        .map(|index| (index, play_game(ruleset, strategy, seed.wrapping_add(index as u64))))
+                        .collect()
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().expect("simulation thread panicked"))
+            .collect()
+    });
+
+    let mut results: Vec<(usize, GameResult)> = chunks.into_iter().flatten().collect();
+    results.sort_by_key(|&(index, _)| index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Nearest rank `percentile`, from 0 to 100, of the ascending `sorted`.
+pub fn percentile<T: Copy>(sorted: &[T], percentile: f64) -> Option<T> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (percentile / 100.0 * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLASSIC: Ruleset = Ruleset {
+        board_size: 4,
+        special_tiles: SpecialTiles::NONE,
+        score_bonuses: ScoreBonuses {
+            combo_bonus: 0,
+            streak_bonus: 0,
+            streak_cap: 0,
+        },
+        limit: ModeLimit::None,
+    };
+
+    #[test]
+    fn percentile_uses_the_nearest_rank() {
+        let sorted: Vec<u32> = (1..=10).collect();
+        assert_eq!(percentile(&sorted, 0.0), Some(1));
+        assert_eq!(percentile(&sorted, 10.0), Some(1));
+        assert_eq!(percentile(&sorted, 50.0), Some(5));
+        assert_eq!(percentile(&sorted, 55.0), Some(6));
+        assert_eq!(percentile(&sorted, 90.0), Some(9));
+        assert_eq!(percentile(&sorted, 99.0), Some(10));
+        assert_eq!(percentile(&sorted, 100.0), Some(10));
+        assert_eq!(percentile::<u32>(&[], 50.0), None);
+    }
+
+    #[test]
+    fn simulate_does_not_depend_on_the_thread_count() {
+        let ruleset = Ruleset {
+            limit: ModeLimit::Moves(150),
+            ..CLASSIC
+        };
+        for strategy in [Strategy::Random, Strategy::Expectimax(1)] {
+            let single = simulate(&ruleset, strategy, 12, 42, 1);
+            assert_eq!(single.len(), 12);
+            for threads in [2, 5, 32] {
+                assert_eq!(simulate(&ruleset, strategy, 12, 42, threads), single);
+            }
+            assert_eq!(single[3], play_game(&ruleset, strategy, 45));
+        }
+    }
+
+    #[test]
+    fn limits_end_the_game() {
+        let moves = Ruleset {
+            limit: ModeLimit::Moves(25),
+            ..CLASSIC
+        };
+        assert_eq!(play_game(&moves, Strategy::Expectimax(1), 7).moves, 25);
+
+        let sprint = Ruleset {
+            limit: ModeLimit::TargetTile(6),
+            ..CLASSIC
+        };
+        let result = play_game(&sprint, Strategy::Expectimax(1), 7);
+        assert!(result.reached_target);
+        assert_eq!(result.top_tile, 6);
+    }
+}
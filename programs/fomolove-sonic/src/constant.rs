@@ -1,9 +1,20 @@
 
 pub const DEFAULT_SEASON_TIMER: u64 = 24 * 60 * 60;
 
-pub const DEFAULT_LEADERBOARD_MAX_GAME_COUNT: u8 = 10;
+/// Largest season leaderboard, see `SeasonLeaderboardAccount`.
+pub const MAX_LEADERBOARD_GAME_COUNT: u16 = 2048;
+/// Entries a single player can hold on a leaderboard, see
+/// `LeaderboardPlayerAccount`.
+pub const MAX_ENTRIES_PER_PLAYER: u8 = 16;
+/// Daily challenge leaderboards live in their account and have to fit the
+/// 10 KiB it can be created with.
+pub const MAX_DAILY_CHALLENGE_GAME_COUNT: u8 = 100;
 pub const DEFAULT_MAX_WINNER_COUNT: u8 = 10;
 
 pub const DEFAULT_MAX_PLAYER_PER_TEAM: u16 = 350;
-pub const DEFAULT_LEADERBOARD_MIN_SCORE: u32 = 0;
-pub const DEFAULT_LEADERBOARD_MIN_TILE: u16 = 2;
\ No newline at end of file
+
+pub const MAX_MOVES_PER_BATCH: u8 = 64;
+
+pub const DAILY_CHALLENGE_DURATION: u64 = 24 * 60 * 60;
+/// Seasons the schedule can hold ahead, see `SeasonScheduleAccount`.
+pub const MAX_SCHEDULED_SEASONS: u8 = 30;
@@ -40,6 +40,126 @@ pub enum FomoLoveErrorCode {
 
     #[msg("Invalid Team")]
     InvalidTeam,
+
+    #[msg("The SlotHashes sysvar has no entries.")]
+    InvalidSlotHashes,
+
+    #[msg("The game is waiting for the oracle to spawn a tile.")]
+    SpawnPending,
+
+    #[msg("The game has no pending spawn.")]
+    NoPendingSpawn,
+
+    #[msg("The game was already submitted.")]
+    GameAlreadySubmitted,
+
+    #[msg("The season of the game ended before it was submitted.")]
+    GameExpired,
+
+    #[msg("The game was registered in another season.")]
+    SeasonMismatch,
+
+    #[msg("Too many moves in one batch.")]
+    TooManyMoves,
+
+    #[msg("The board size must be between 3 and 8.")]
+    InvalidBoardSize,
+
+    #[msg("The game mode rules are invalid.")]
+    InvalidModeRules,
+
+    #[msg("This game mode is not enabled for the season.")]
+    ModeNotEnabled,
+
+    #[msg("The time limit of the game is over.")]
+    TimeUp,
+
+    #[msg("The game did not reach the sprint target tile.")]
+    SprintTargetNotReached,
+
+    #[msg("Too many moves to enter the sprint leaderboard.")]
+    TooManySprintMoves,
+
+    #[msg("The special tile chances are invalid.")]
+    InvalidSpecialTiles,
+
+    #[msg("Seasons cannot use the challenge randomness mode.")]
+    InvalidRandomnessMode,
+
+    #[msg("The challenge is not for the current day of the season.")]
+    InvalidChallengeDay,
+
+    #[msg("The daily challenge is closed.")]
+    ChallengeClosed,
+
+    #[msg("Daily challenge games are Classic games.")]
+    InvalidChallengeMode,
+
+    #[msg("Daily challenge games go to the leaderboard of their day.")]
+    DailyChallengeGame,
+
+    #[msg("The game was not registered for this daily challenge.")]
+    ChallengeMismatch,
+
+    #[msg("The combo and streak bonuses are too large.")]
+    InvalidScoringRules,
+
+    #[msg("The leaderboard size is out of range.")]
+    InvalidLeaderboardSize,
+
+    #[msg("The leaderboard minimum tile is not a tile value.")]
+    InvalidMinTile,
+
+    #[msg("The leaderboard of this mode has already been created.")]
+    LeaderboardExists,
+
+    #[msg("The leaderboard is not the one of the game mode.")]
+    LeaderboardMismatch,
+
+    #[msg("A season has to allow at least one leaderboard entry per player.")]
+    InvalidEntriesPerPlayer,
+
+    #[msg("The game does not rank above the player's own leaderboard entries.")]
+    PlayerEntryNotImproved,
+
+    #[msg("The season has been finalized.")]
+    SeasonFinalized,
+
+    #[msg("No season id is left.")]
+    SeasonIdOverflow,
+
+    #[msg("The account is not a legacy account with a u8 season id.")]
+    InvalidLegacyAccount,
+
+    #[msg("The season schedule is full.")]
+    ScheduleFull,
+
+    #[msg("Scheduled seasons must start in order.")]
+    InvalidSchedule,
+
+    #[msg("No season is scheduled.")]
+    ScheduleEmpty,
+
+    #[msg("The season is paused.")]
+    SeasonPaused,
+
+    #[msg("The season is not paused.")]
+    SeasonNotPaused,
+
+    #[msg("Only the current season can be changed.")]
+    NotCurrentSeason,
+
+    #[msg("The season has to end after now and after its start.")]
+    InvalidSeasonEnd,
+
+    #[msg("The VRF proof does not verify under the randomness oracle.")]
+    InvalidVrfProof,
+
+    #[msg("Scheduled leaderboards have to be empty program accounts, queued once, for the enabled modes only.")]
+    InvalidScheduledLeaderboard,
+
+    #[msg("No season is scheduled at this position.")]
+    ScheduledSeasonNotFound,
 }
 
 #[error_code]
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+/// Randomness behind the spawns of a game from move `first_move` on, until
+/// the next event of the same game. Replays of SlotHashes and oracle games
+/// read it back from the transaction logs.
+#[event]
+pub struct SpawnRandomness {
+    pub game: Pubkey,
+    pub first_move: u32,
+    pub randomness: [u8; 32],
+}
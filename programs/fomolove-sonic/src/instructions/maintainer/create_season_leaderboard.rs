@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::FomoLoveErrorCode, ConfigAccount, GameMode, SeasonAccount, SeasonLeaderboardAccount};
+
+#[derive(Accounts)]
+pub struct CreateSeasonLeaderboard<'info> {
+    pub maintainer: Signer<'info>,
+    #[account(
+        constraint = config_account.maintainer == maintainer.key() @ FomoLoveErrorCode::Unauthorized
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+    #[account(mut)]
+    pub season_account: Account<'info, SeasonAccount>,
+    /// Created by the client with `SeasonLeaderboardAccount::space_for` bytes
+    #[account(zero)]
+    pub leaderboard: AccountLoader<'info, SeasonLeaderboardAccount>,
+}
+
+/// Attaches the leaderboard of `mode` to the season, once per mode.
+pub fn create_season_leaderboard(ctx: Context<CreateSeasonLeaderboard>, mode: GameMode) -> Result<()> {
+    let season_key = ctx.accounts.season_account.key();
+    let season_account = &mut ctx.accounts.season_account;
+    let params = season_account.params;
+
+    require!(!season_account.finalized, FomoLoveErrorCode::SeasonFinalized);
+    require!(params.mode_rules.is_enabled(mode), FomoLoveErrorCode::ModeNotEnabled);
+    require!(
+        season_account.leaderboards[mode.index()] == Pubkey::default(),
+        FomoLoveErrorCode::LeaderboardExists
+    );
+    require!(
+        ctx.accounts.leaderboard.as_ref().data_len() == SeasonLeaderboardAccount::space_for(params.leaderboard_size),
+        FomoLoveErrorCode::InvalidLeaderboardSize
+    );
+
+    ctx.accounts.leaderboard.load_init()?.init(season_key, mode, &params);
+    season_account.leaderboards[mode.index()] = ctx.accounts.leaderboard.key();
+
+    Ok(())
+}
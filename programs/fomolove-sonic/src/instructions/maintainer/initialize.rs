@@ -1,7 +1,7 @@
 use anchor_lang::prelude::*;
 use std::ops::DerefMut;
 
-use crate::{error::FomoLoveErrorCode, ConfigAccount, TeamAccount, TeamType, WinnerAccount, DEFAULT_MAX_WINNER_COUNT, DEFAULT_SEASON_TIMER};
+use crate::{error::FomoLoveErrorCode, ConfigAccount, RandomnessMode, TeamAccount, TeamType, WinnerAccount, DEFAULT_MAX_WINNER_COUNT, DEFAULT_SEASON_TIMER};
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
@@ -55,6 +55,8 @@ pub fn initialize(ctx: Context<Initialize>, chain_team_base_url: String, meme_te
     config_account.current_season_id = 0;
     config_account.season_duration = DEFAULT_SEASON_TIMER;
     config_account.current_season_ended_at = Clock::get()?.unix_timestamp as u64;
+    config_account.randomness_mode = RandomnessMode::SlotHashes;
+    config_account.randomness_oracle = Pubkey::default();
 
     config_account.is_initialized = true; // Set the account as initialized
 
@@ -0,0 +1,71 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    error::FomoLoveErrorCode, ConfigAccount, GameMode, LegacySeasonAccount, SeasonAccount, SeasonLeaderboard,
+    SeasonLeaderboardAccount,
+};
+
+#[derive(Accounts)]
+#[instruction(legacy_season_id: u8)]
+pub struct MigrateSeason<'info> {
+    #[account(mut)]
+    pub maintainer: Signer<'info>,
+    #[account(
+        constraint = config_account.maintainer == maintainer.key() @ FomoLoveErrorCode::Unauthorized
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+    /// CHECK: Legacy `SeasonAccount`, decoded by `LegacySeasonAccount::read`
+    #[account(mut, seeds = [b"season".as_ref(), &[legacy_season_id]], bump)]
+    pub legacy_season: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = maintainer,
+        space = SeasonAccount::INIT_SPACE,
+        seeds = [b"season".as_ref(), &(legacy_season_id as u32).to_le_bytes()],
+        bump
+    )]
+    pub season_account: Account<'info, SeasonAccount>,
+    /// Classic leaderboard the legacy games move to, created by the client
+    /// with `SeasonLeaderboardAccount::space_for` bytes
+    #[account(zero)]
+    pub leaderboard: AccountLoader<'info, SeasonLeaderboardAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Moves a season written under its `u8` seed to the `u32` one, copies its
+/// leaderboard to a classic `SeasonLeaderboardAccount` and closes the legacy
+/// account. Games, user seasons and daily challenges of legacy seasons are
+/// not migrated, the upgrade is meant to ship between seasons.
+pub fn migrate_season(ctx: Context<MigrateSeason>, legacy_season_id: u8) -> Result<()> {
+    let legacy_season = LegacySeasonAccount::read(&ctx.accounts.legacy_season)?;
+    require!(
+        legacy_season.season_id == legacy_season_id,
+        FomoLoveErrorCode::InvalidLegacyAccount
+    );
+
+    let params = legacy_season.params();
+    require!(
+        ctx.accounts.leaderboard.as_ref().data_len() == SeasonLeaderboardAccount::space_for(params.leaderboard_size),
+        FomoLoveErrorCode::InvalidLeaderboardSize
+    );
+    let season_key = ctx.accounts.season_account.key();
+    ctx.accounts
+        .leaderboard
+        .load_init()?
+        .init(season_key, GameMode::Classic, &params);
+    SeasonLeaderboard::load_mut(&ctx.accounts.leaderboard)?.restore(&legacy_season.ranked_games())?;
+
+    let leaderboard_key = ctx.accounts.leaderboard.key();
+    ctx.accounts
+        .season_account
+        .set_inner(legacy_season.migrate(ctx.bumps.season_account, leaderboard_key));
+
+    // Close the legacy account, its rent goes back to the maintainer
+    let legacy_info = ctx.accounts.legacy_season.to_account_info();
+    let maintainer_info = ctx.accounts.maintainer.to_account_info();
+    **maintainer_info.lamports.borrow_mut() += legacy_info.lamports();
+    **legacy_info.lamports.borrow_mut() = 0;
+    legacy_info.assign(&system_program::ID);
+    legacy_info.realloc(0, false)?;
+    Ok(())
+}
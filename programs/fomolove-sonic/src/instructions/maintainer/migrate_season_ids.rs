@@ -0,0 +1,70 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{error::FomoLoveErrorCode, ConfigAccount, LegacyConfigAccount, LegacyWinnerAccount, WinnerAccount};
+
+#[derive(Accounts)]
+pub struct MigrateSeasonIds<'info> {
+    #[account(mut)]
+    pub maintainer: Signer<'info>,
+    /// CHECK: Legacy `ConfigAccount`, decoded by `LegacyConfigAccount::read`
+    #[account(mut, seeds = [b"config".as_ref()], bump)]
+    pub config_account: UncheckedAccount<'info>,
+    /// CHECK: Legacy `WinnerAccount`, decoded by `LegacyWinnerAccount::read`
+    #[account(mut, seeds = [b"winner".as_ref()], bump)]
+    pub winner_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Rewrites the config and winner accounts with `u32` season ids. Runs once
+/// after the upgrade, before `migrate_season` and any other instruction.
+pub fn migrate_season_ids(ctx: Context<MigrateSeasonIds>) -> Result<()> {
+    let legacy_config = LegacyConfigAccount::read(&ctx.accounts.config_account)?;
+    require_keys_eq!(
+        legacy_config.maintainer,
+        ctx.accounts.maintainer.key(),
+        FomoLoveErrorCode::Unauthorized
+    );
+    let legacy_winner = LegacyWinnerAccount::read(&ctx.accounts.winner_account)?;
+
+    rewrite_account(
+        &ctx,
+        &ctx.accounts.config_account,
+        ConfigAccount::INIT_SPACE,
+        &legacy_config.migrate(),
+    )?;
+    rewrite_account(
+        &ctx,
+        &ctx.accounts.winner_account,
+        WinnerAccount::INIT_SPACE,
+        &legacy_winner.migrate(),
+    )?;
+    Ok(())
+}
+
+/// Grows a legacy account to `space`, the maintainer topping up its rent, and
+/// writes `account` over it.
+fn rewrite_account<'info, T: AccountSerialize>(
+    ctx: &Context<MigrateSeasonIds<'info>>,
+    account_info: &AccountInfo<'info>,
+    space: usize,
+    account: &T,
+) -> Result<()> {
+    let rent = Rent::get()?.minimum_balance(space);
+    if rent > account_info.lamports() {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.maintainer.to_account_info(),
+                    to: account_info.clone(),
+                },
+            ),
+            rent - account_info.lamports(),
+        )?;
+    }
+    account_info.realloc(space, false)?;
+
+    let mut data = account_info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data;
+    account.try_serialize(&mut writer)
+}
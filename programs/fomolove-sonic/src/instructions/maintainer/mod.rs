@@ -4,5 +4,34 @@ pub use initialize::*;
 pub mod start_season;
 pub use start_season::*;
 
+pub mod create_season_leaderboard;
+pub use create_season_leaderboard::*;
+
 pub mod update_season_duration;
-pub use update_season_duration::*;
\ No newline at end of file
+pub use update_season_duration::*;
+
+pub mod update_randomness_provider;
+pub use update_randomness_provider::*;
+pub mod migrate_season_ids;
+pub use migrate_season_ids::*;
+
+pub mod migrate_season;
+pub use migrate_season::*;
+
+pub mod schedule_seasons;
+pub use schedule_seasons::*;
+
+pub mod unschedule_season;
+pub use unschedule_season::*;
+
+pub mod pause_season;
+pub use pause_season::*;
+
+pub mod resume_season;
+pub use resume_season::*;
+
+pub mod update_season_end;
+pub use update_season_end::*;
+
+pub mod withdraw_entry_fees;
+pub use withdraw_entry_fees::*;
@@ -0,0 +1,32 @@
+use std::ops::DerefMut;
+
+use anchor_lang::prelude::*;
+
+use crate::{check_season_ended, error::FomoLoveErrorCode, ConfigAccount, SeasonAccount};
+
+#[derive(Accounts)]
+pub struct PauseSeason<'info> {
+    pub maintainer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = config_account.maintainer == maintainer.key() @ FomoLoveErrorCode::Unauthorized
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+    #[account(
+        mut,
+        constraint = season_account.season_id == config_account.current_season_id @ FomoLoveErrorCode::NotCurrentSeason
+    )]
+    pub season_account: Account<'info, SeasonAccount>,
+}
+
+/// Stops play on the running season until `resume_season`. The end of the
+/// season is unknown until then, so the next one can not be opened. Time
+/// Attack deadlines are pushed back by the pause once it is over.
+pub fn pause_season(ctx: Context<PauseSeason>) -> Result<()> {
+    let season_account = ctx.accounts.season_account.deref_mut();
+    check_season_ended(season_account)?;
+
+    season_account.paused_at = Clock::get()?.unix_timestamp as u64;
+    ctx.accounts.config_account.current_season_ended_at = u64::MAX;
+    Ok(())
+}
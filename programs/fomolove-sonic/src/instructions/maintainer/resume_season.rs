@@ -0,0 +1,37 @@
+use std::ops::DerefMut;
+
+use anchor_lang::prelude::*;
+
+use crate::{error::FomoLoveErrorCode, ConfigAccount, SeasonAccount};
+
+#[derive(Accounts)]
+pub struct ResumeSeason<'info> {
+    pub maintainer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = config_account.maintainer == maintainer.key() @ FomoLoveErrorCode::Unauthorized
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+    #[account(
+        mut,
+        constraint = season_account.season_id == config_account.current_season_id @ FomoLoveErrorCode::NotCurrentSeason
+    )]
+    pub season_account: Account<'info, SeasonAccount>,
+}
+
+/// Reopens a paused season, its end pushed back by the time it was paused.
+/// TimeAttack games catch up on their next move.
+pub fn resume_season(ctx: Context<ResumeSeason>) -> Result<()> {
+    let config_account = ctx.accounts.config_account.deref_mut();
+    let season_account = ctx.accounts.season_account.deref_mut();
+    require!(season_account.paused_at != 0, FomoLoveErrorCode::SeasonNotPaused);
+
+    let now = Clock::get()?.unix_timestamp as u64;
+    let paused_time = now - season_account.paused_at;
+    season_account.ended_at += paused_time;
+    season_account.paused_time += paused_time;
+    season_account.paused_at = 0;
+    config_account.current_season_ended_at = season_account.ended_at;
+
+    Ok(())
+}
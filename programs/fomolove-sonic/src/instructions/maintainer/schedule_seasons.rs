@@ -0,0 +1,110 @@
+use std::ops::DerefMut;
+
+use anchor_lang::{prelude::*, system_program};
+
+use crate::{
+    error::FomoLoveErrorCode, ConfigAccount, GameMode, ScheduledSeason, SeasonLeaderboardAccount, SeasonScheduleAccount,
+    MAX_SCHEDULED_SEASONS,
+};
+
+#[derive(Accounts)]
+pub struct ScheduleSeasons<'info> {
+    #[account(mut)]
+    pub maintainer: Signer<'info>,
+    #[account(
+        constraint = config_account.maintainer == maintainer.key() @ FomoLoveErrorCode::Unauthorized
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+    #[account(
+        init_if_needed,
+        payer = maintainer,
+        space = SeasonScheduleAccount::INIT_SPACE,
+        seeds = [b"season_schedule".as_ref()],
+        bump
+    )]
+    pub season_schedule: Account<'info, SeasonScheduleAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Queues `seasons` after the scheduled ones and sets the crank reward. The
+/// maintainer funds the reward of every queued season up front, and passes
+/// the leaderboards of the queued seasons as remaining accounts, season by
+/// season in `GameMode` order.
+pub fn schedule_seasons(ctx: Context<ScheduleSeasons>, seasons: Vec<ScheduledSeason>, crank_reward: u64) -> Result<()> {
+    let schedule = ctx.accounts.season_schedule.deref_mut();
+    schedule.bump = ctx.bumps.season_schedule;
+    schedule.crank_reward = crank_reward;
+
+    require!(
+        schedule.seasons.len() + seasons.len() <= MAX_SCHEDULED_SEASONS as usize,
+        FomoLoveErrorCode::ScheduleFull
+    );
+    let mut last_start_time = schedule.seasons.last().map(|season| season.start_time);
+    let mut queued_leaderboards: Vec<Pubkey> = schedule
+        .seasons
+        .iter()
+        .flat_map(|season| season.leaderboards)
+        .filter(|key| *key != Pubkey::default())
+        .collect();
+    let mut leaderboards = ctx.remaining_accounts.iter();
+    for season in &seasons {
+        season.params.validate()?;
+        require!(
+            last_start_time.map_or(true, |last| last < season.start_time),
+            FomoLoveErrorCode::InvalidSchedule
+        );
+        last_start_time = Some(season.start_time);
+
+        for mode in GameMode::ALL {
+            let key = season.leaderboards[mode.index()];
+            if !season.params.mode_rules.is_enabled(mode) {
+                require_keys_eq!(key, Pubkey::default(), FomoLoveErrorCode::InvalidScheduledLeaderboard);
+                continue;
+            }
+            let account_info = leaderboards.next().ok_or(FomoLoveErrorCode::LeaderboardMismatch)?;
+            require_keys_eq!(account_info.key(), key, FomoLoveErrorCode::LeaderboardMismatch);
+            require!(
+                !queued_leaderboards.contains(&key),
+                FomoLoveErrorCode::InvalidScheduledLeaderboard
+            );
+            check_empty_leaderboard(account_info, season.params.leaderboard_size)?;
+            queued_leaderboards.push(key);
+        }
+    }
+    require!(leaderboards.next().is_none(), FomoLoveErrorCode::LeaderboardMismatch);
+    schedule.seasons.extend(seasons);
+
+    // Rewards already funded stay on the account
+    let rent = Rent::get()?.minimum_balance(SeasonScheduleAccount::INIT_SPACE);
+    let funded = ctx.accounts.season_schedule.get_lamports().saturating_sub(rent);
+    let needed = crank_reward.saturating_mul(ctx.accounts.season_schedule.seasons.len() as u64);
+    if needed > funded {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.maintainer.to_account_info(),
+                    to: ctx.accounts.season_schedule.to_account_info(),
+                },
+            ),
+            needed - funded,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// A leaderboard account the crank can initialize: owned by the program,
+/// sized for `leaderboard_size` games and never initialized.
+fn check_empty_leaderboard(account_info: &AccountInfo, leaderboard_size: u16) -> Result<()> {
+    require_keys_eq!(*account_info.owner, crate::ID, FomoLoveErrorCode::InvalidScheduledLeaderboard);
+    require!(
+        account_info.data_len() == SeasonLeaderboardAccount::space_for(leaderboard_size),
+        FomoLoveErrorCode::InvalidLeaderboardSize
+    );
+    require!(
+        account_info.try_borrow_data()?[..8].iter().all(|byte| *byte == 0),
+        FomoLoveErrorCode::InvalidScheduledLeaderboard
+    );
+    Ok(())
+}
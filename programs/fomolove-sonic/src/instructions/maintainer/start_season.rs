@@ -2,7 +2,7 @@ use std::ops::DerefMut;
 
 use anchor_lang::prelude::*;
 
-use crate::{error::FomoLoveErrorCode, ConfigAccount, LeaderBoard, SeasonAccount, DEFAULT_LEADERBOARD_MAX_GAME_COUNT, DEFAULT_LEADERBOARD_MIN_SCORE, DEFAULT_LEADERBOARD_MIN_TILE};
+use crate::{error::FomoLoveErrorCode, ConfigAccount, SeasonAccount, SeasonParams, GAME_MODE_COUNT};
 
 #[derive(Accounts)]
 pub struct StartSeason<'info> {
@@ -17,36 +17,59 @@ pub struct StartSeason<'info> {
       init_if_needed,
       payer = maintainer,
       space = SeasonAccount::INIT_SPACE,
-      seeds = [b"season".as_ref(), &[config_account.current_season_id + 1]],
+      seeds = [b"season".as_ref(), &config_account.current_season_id.saturating_add(1).to_le_bytes()],
       bump
     )]
     pub season_account: Account<'info, SeasonAccount>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn start_season(ctx: Context<StartSeason>, start_time: u64) -> Result<()> {
-    let config_account = ctx.accounts.config_account.deref_mut();
+pub fn start_season(ctx: Context<StartSeason>, start_time: u64, params: SeasonParams) -> Result<()> {
+    open_season(
+        ctx.accounts.config_account.deref_mut(),
+        ctx.accounts.season_account.deref_mut(),
+        ctx.bumps.season_account,
+        start_time,
+        params,
+    )
+}
 
-    let season_account = ctx.accounts.season_account.deref_mut();
-    
+/// Opens the next season at `start_time` once the current one has ended,
+/// shared by `start_season` and `crank_next_season`.
+pub fn open_season(
+    config_account: &mut ConfigAccount,
+    season_account: &mut SeasonAccount,
+    bump: u8,
+    start_time: u64,
+    params: SeasonParams,
+) -> Result<()> {
     let now = Clock::get()?.unix_timestamp as u64;
 
     require!(start_time >= config_account.current_season_ended_at && now >= config_account.current_season_ended_at, FomoLoveErrorCode::SeasonNotEnded);
+    params.validate()?;
+    let season_id = config_account
+        .current_season_id
+        .checked_add(1)
+        .ok_or(FomoLoveErrorCode::SeasonIdOverflow)?;
+
+    let duration = params.duration_or(config_account.season_duration);
+    require!(start_time.saturating_add(duration) > now, FomoLoveErrorCode::InvalidSeasonEnd);
 
     // Initialize the season account
-    season_account.bump = ctx.bumps.season_account;
+    season_account.bump = bump;
     season_account.started_at = start_time;
-    season_account.ended_at = start_time + config_account.season_duration;
-    season_account.season_id = config_account.current_season_id + 1;
-    season_account.leaderboard = LeaderBoard {
-        min_score: DEFAULT_LEADERBOARD_MIN_SCORE,
-        min_tile: DEFAULT_LEADERBOARD_MIN_TILE,
-        top_games: Vec::new(),
-        max_game_count: DEFAULT_LEADERBOARD_MAX_GAME_COUNT,
-    };
-
-    config_account.current_season_id = config_account.current_season_id + 1;
-    config_account.current_season_ended_at = start_time + config_account.season_duration;
+    season_account.ended_at = start_time + duration;
+    season_account.season_id = season_id;
+    season_account.randomness_mode = config_account.randomness_mode;
+    season_account.params = SeasonParams { duration, ..params };
+    season_account.entry_fees = 0;
+    season_account.finalized = false;
+    season_account.paused_at = 0;
+    season_account.paused_time = 0;
+    season_account.leaderboards = [Pubkey::default(); GAME_MODE_COUNT];
+
+    config_account.current_season_id = season_id;
+    config_account.current_season_ended_at = start_time + duration;
 
     Ok(())
 }
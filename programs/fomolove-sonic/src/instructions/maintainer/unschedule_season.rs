@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::FomoLoveErrorCode, ConfigAccount, SeasonScheduleAccount};
+
+#[derive(Accounts)]
+pub struct UnscheduleSeason<'info> {
+    pub maintainer: Signer<'info>,
+    #[account(
+        constraint = config_account.maintainer == maintainer.key() @ FomoLoveErrorCode::Unauthorized
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+    #[account(
+        mut,
+        seeds = [b"season_schedule".as_ref()],
+        bump = season_schedule.bump
+    )]
+    pub season_schedule: Account<'info, SeasonScheduleAccount>,
+}
+
+/// Drops the scheduled season at `index`, 0 being the next one. Unblocks the
+/// crank when a queued leaderboard can no longer be initialized, e.g. because
+/// it was attached to another season since. Its leaderboards stay with the
+/// maintainer and its funded crank reward stays on the schedule.
+pub fn unschedule_season(ctx: Context<UnscheduleSeason>, index: u8) -> Result<()> {
+    let schedule = &mut ctx.accounts.season_schedule;
+    require!(
+        (index as usize) < schedule.seasons.len(),
+        FomoLoveErrorCode::ScheduledSeasonNotFound
+    );
+    schedule.seasons.remove(index as usize);
+
+    Ok(())
+}
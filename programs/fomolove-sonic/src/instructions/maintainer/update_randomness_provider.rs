@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use std::ops::DerefMut;
+
+use crate::{error::FomoLoveErrorCode, ConfigAccount, RandomnessMode};
+
+#[derive(Accounts)]
+pub struct UpdateRandomnessProvider<'info> {
+    #[account(mut)]
+    pub maintainer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = maintainer.key() == config_account.maintainer.key()
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Selects the randomness provider used by seasons started from now on.
+pub fn update_randomness_provider(ctx: Context<UpdateRandomnessProvider>, randomness_mode: RandomnessMode, randomness_oracle: Pubkey) -> Result<()> {
+    require!(randomness_mode != RandomnessMode::Challenge, FomoLoveErrorCode::InvalidRandomnessMode);
+
+    let config_account = ctx.accounts.config_account.deref_mut();
+    config_account.randomness_mode = randomness_mode;
+    config_account.randomness_oracle = randomness_oracle;
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use std::ops::DerefMut;
+
+use anchor_lang::prelude::*;
+
+use crate::{check_season_ended, error::FomoLoveErrorCode, ConfigAccount, SeasonAccount};
+
+#[derive(Accounts)]
+pub struct UpdateSeasonEnd<'info> {
+    pub maintainer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = config_account.maintainer == maintainer.key() @ FomoLoveErrorCode::Unauthorized
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+    #[account(
+        mut,
+        constraint = season_account.season_id == config_account.current_season_id @ FomoLoveErrorCode::NotCurrentSeason
+    )]
+    pub season_account: Account<'info, SeasonAccount>,
+}
+
+/// Extends or shortens the running season. It can not be made to end in the
+/// past, games in play keep a chance to be submitted.
+pub fn update_season_end(ctx: Context<UpdateSeasonEnd>, ended_at: u64) -> Result<()> {
+    let config_account = ctx.accounts.config_account.deref_mut();
+    let season_account = ctx.accounts.season_account.deref_mut();
+    check_season_ended(season_account)?;
+
+    let now = Clock::get()?.unix_timestamp as u64;
+    require!(
+        ended_at > now && ended_at > season_account.started_at,
+        FomoLoveErrorCode::InvalidSeasonEnd
+    );
+
+    season_account.ended_at = ended_at;
+    config_account.current_season_ended_at = ended_at;
+
+    Ok(())
+}
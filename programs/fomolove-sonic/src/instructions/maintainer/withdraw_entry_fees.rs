@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::FomoLoveErrorCode, ConfigAccount, SeasonAccount, SeasonResultAccount};
+
+#[derive(Accounts)]
+pub struct WithdrawEntryFees<'info> {
+    pub maintainer: Signer<'info>,
+    #[account(
+        seeds = [b"config".as_ref()],
+        bump = config_account.bump,
+        constraint = config_account.maintainer == maintainer.key() @ FomoLoveErrorCode::Unauthorized
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+    #[account(
+        mut,
+        seeds = [b"season".as_ref(), &season_account.season_id.to_le_bytes()],
+        bump = season_account.bump
+    )]
+    pub season_account: Account<'info, SeasonAccount>,
+    /// Only exists once `finalize_season` has frozen the season
+    #[account(
+        seeds = [b"season_result".as_ref(), &season_account.season_id.to_le_bytes()],
+        bump = season_result.bump
+    )]
+    pub season_result: Account<'info, SeasonResultAccount>,
+    /// CHECK: only receives lamports, the prize pool or a reward distributor
+    #[account(mut)]
+    pub recipient: UncheckedAccount<'info>,
+}
+
+/// Moves the entry fees collected by a finalized season to `recipient`, to be
+/// paid out along the `SeasonResultAccount` ranks.
+pub fn withdraw_entry_fees(ctx: Context<WithdrawEntryFees>) -> Result<()> {
+    require!(ctx.accounts.season_account.finalized, FomoLoveErrorCode::SeasonNotEnded);
+
+    let amount = ctx.accounts.season_account.entry_fees;
+    ctx.accounts.season_account.entry_fees = 0;
+    ctx.accounts.season_account.sub_lamports(amount)?;
+    ctx.accounts.recipient.add_lamports(amount)?;
+
+    Ok(())
+}
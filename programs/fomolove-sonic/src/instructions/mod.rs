@@ -2,4 +2,7 @@ pub mod maintainer;
 pub use maintainer::*;
 
 pub mod user;
-pub use user::*;
\ No newline at end of file
+pub use user::*;
+
+pub mod oracle;
+pub use oracle::*;
\ No newline at end of file
@@ -0,0 +1,102 @@
+use std::ops::DerefMut;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::FomoLoveErrorCode, events::SpawnRandomness, record_progress, vrf::VRF_PROOF_LEN, ConfigAccount,
+    GameAccount, GameStatus, ProgressAccounts, RandomnessProvider, TeamAccount, TeamSeasonAccount, UserAccount,
+    UserSeasonAccount, VrfProvider, WinnerAccount,
+};
+
+#[derive(Accounts)]
+pub struct FulfillSpawn<'info> {
+    #[account(
+        seeds = [b"config"],
+        bump = config_account.bump
+    )]
+    pub config_account: Account<'info, ConfigAccount>,
+    #[account(
+        seeds = [b"user".as_ref(), game.owner.as_ref()],
+        bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    #[account(mut)]
+    pub winner_account: Account<'info, WinnerAccount>,
+    #[account(
+        constraint = user_team_account.team_type == user_account.team @ FomoLoveErrorCode::TeamTypeMismatch
+    )]
+    pub user_team_account: Account<'info, TeamAccount>,
+    #[account(
+        mut,
+        seeds = [b"user_season".as_ref(), game.owner.as_ref(), &game.season_id.to_le_bytes()],
+        bump
+    )]
+    pub user_season_account: Account<'info, UserSeasonAccount>,
+    #[account(
+        mut,
+        seeds = [b"team_season".as_ref(), &[user_account.team as u8], &game.season_id.to_le_bytes()],
+        bump = team_season_account.bump
+    )]
+    pub team_season_account: Account<'info, TeamSeasonAccount>,
+    #[account(mut,
+        constraint = game.nft_mint.key() == nft_mint.key()
+    )]
+    pub game: Account<'info, GameAccount>,
+    #[account(mut)]
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+    pub token_program: Program<'info, Token2022>,
+}
+
+/// Reveals a pending spawn from the oracle's VRF proof. Anyone may submit the
+/// proof, since only the oracle can produce it and it has a single output.
+/// The spawn is recorded like a move, see `record_progress`.
+pub fn fulfill_spawn(ctx: Context<FulfillSpawn>, proof: [u8; VRF_PROOF_LEN]) -> Result<()> {
+    let game_key = ctx.accounts.game.key();
+
+    // A game submitted or expired while waiting keeps its board as it was
+    require!(ctx.accounts.game.status == GameStatus::Active, FomoLoveErrorCode::GameOver);
+    require!(ctx.accounts.game.pending_spawn, FomoLoveErrorCode::NoPendingSpawn);
+
+    let alpha = VrfProvider::alpha(&game_key, ctx.accounts.game.move_count);
+    let randomness = VrfProvider {
+        oracle: &ctx.accounts.config_account.randomness_oracle,
+        alpha: &alpha,
+        proof: &proof,
+    }
+    .randomness()?;
+
+    let game = ctx.accounts.game.deref_mut();
+    let old_top_tile = game.top_tile;
+    let old_score = game.score;
+
+    emit!(SpawnRandomness {
+        game: game_key,
+        first_move: game.move_count,
+        randomness,
+    });
+
+    let mut entropy = game.spawn_entropy(&game_key, &randomness);
+    game.add_new_tile(&mut entropy)?;
+    game.pending_spawn = false;
+
+    game.finish_if_over()?;
+
+    let accounts = ctx.accounts;
+    record_progress(
+        ProgressAccounts {
+            player: accounts.game.owner,
+            config_account: &accounts.config_account,
+            user_account: &accounts.user_account,
+            winner_account: &mut accounts.winner_account,
+            user_team_account: &accounts.user_team_account,
+            user_season_account: &mut accounts.user_season_account,
+            team_season_account: &mut accounts.team_season_account,
+            game: &accounts.game,
+            nft_mint: &accounts.nft_mint,
+        },
+        old_top_tile,
+        old_score,
+    )
+}
@@ -0,0 +1,2 @@
+pub mod fulfill_spawn;
+pub use fulfill_spawn::*;
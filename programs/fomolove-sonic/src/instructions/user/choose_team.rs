@@ -45,6 +45,7 @@ pub fn choose_team(ctx: Context<ChooseTeam>, team_type: TeamType) -> Result<()>
         TeamType::None => return Err(FomoLoveErrorCode::InvalidTeam.into()),
     }
     //handle user
+    user_account.bump = ctx.bumps.user_account;
     user_account.team = team_type;
 
     Ok(())
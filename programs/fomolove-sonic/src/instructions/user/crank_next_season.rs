@@ -0,0 +1,85 @@
+use std::ops::DerefMut;
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::FomoLoveErrorCode, open_season, ConfigAccount, GameMode, SeasonAccount, SeasonLeaderboardAccount,
+    SeasonScheduleAccount,
+};
+
+#[derive(Accounts)]
+pub struct CrankNextSeason<'info> {
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+    #[account(mut)]
+    pub config_account: Account<'info, ConfigAccount>,
+    #[account(
+        mut,
+        seeds = [b"season_schedule".as_ref()],
+        bump = season_schedule.bump
+    )]
+    pub season_schedule: Account<'info, SeasonScheduleAccount>,
+    #[account(
+        init,
+        payer = cranker,
+        space = SeasonAccount::INIT_SPACE,
+        seeds = [b"season".as_ref(), &config_account.current_season_id.saturating_add(1).to_le_bytes()],
+        bump
+    )]
+    pub season_account: Account<'info, SeasonAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Starts the next scheduled season once the current one has ended. Anyone
+/// can call it, passing the scheduled leaderboards of the season as remaining
+/// accounts in `GameMode` order. The cranker pays the season account and is
+/// paid the crank reward while the schedule holds enough lamports for it.
+pub fn crank_next_season<'info>(ctx: Context<'_, '_, 'info, 'info, CrankNextSeason<'info>>) -> Result<()> {
+    let schedule = ctx.accounts.season_schedule.deref_mut();
+    require!(!schedule.seasons.is_empty(), FomoLoveErrorCode::ScheduleEmpty);
+    let next = schedule.seasons.remove(0);
+    let crank_reward = schedule.crank_reward;
+
+    let config_account = ctx.accounts.config_account.deref_mut();
+    let now = Clock::get()?.unix_timestamp as u64;
+    let duration = next.params.duration_or(config_account.season_duration);
+    let mut start_time = next.start_time.max(config_account.current_season_ended_at);
+    // A season cranked too late to be played at all runs from now instead
+    if start_time.saturating_add(duration) <= now {
+        start_time = now;
+    }
+    open_season(
+        config_account,
+        ctx.accounts.season_account.deref_mut(),
+        ctx.bumps.season_account,
+        start_time,
+        next.params,
+    )?;
+
+    let season_key = ctx.accounts.season_account.key();
+    let season_account = ctx.accounts.season_account.deref_mut();
+    let mut remaining_accounts = ctx.remaining_accounts.iter();
+    for mode in GameMode::ALL {
+        let key = next.leaderboards[mode.index()];
+        if key == Pubkey::default() {
+            continue;
+        }
+        let account_info = remaining_accounts.next().ok_or(FomoLoveErrorCode::LeaderboardMismatch)?;
+        require_keys_eq!(account_info.key(), key, FomoLoveErrorCode::LeaderboardMismatch);
+
+        let leaderboard = AccountLoader::<SeasonLeaderboardAccount>::try_from_unchecked(&crate::ID, account_info)?;
+        leaderboard.load_init()?.init(season_key, mode, &season_account.params);
+        leaderboard.exit(&crate::ID)?;
+        season_account.leaderboards[mode.index()] = key;
+    }
+    require!(remaining_accounts.next().is_none(), FomoLoveErrorCode::LeaderboardMismatch);
+
+    let rent = Rent::get()?.minimum_balance(SeasonScheduleAccount::INIT_SPACE);
+    let reward = crank_reward.min(ctx.accounts.season_schedule.get_lamports().saturating_sub(rent));
+    if reward > 0 {
+        ctx.accounts.season_schedule.sub_lamports(reward)?;
+        ctx.accounts.cranker.add_lamports(reward)?;
+    }
+
+    Ok(())
+}
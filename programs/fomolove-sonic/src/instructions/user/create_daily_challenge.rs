@@ -0,0 +1,58 @@
+use std::ops::DerefMut;
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use crate::{
+    check_season_ended, error::FomoLoveErrorCode, recent_slot_hash, DailyChallengeAccount, GameMode,
+    LeaderBoard, SeasonAccount, DAILY_CHALLENGE_DURATION,
+};
+
+#[derive(Accounts)]
+#[instruction(day: u32)]
+pub struct CreateDailyChallenge<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub season_account: Account<'info, SeasonAccount>,
+    #[account(
+        init,
+        payer = payer,
+        space = DailyChallengeAccount::space_for(season_account.params.daily_challenge_size()),
+        seeds = [b"daily_challenge".as_ref(), &season_account.season_id.to_le_bytes(), &day.to_le_bytes()],
+        bump
+    )]
+    pub daily_challenge: Account<'info, DailyChallengeAccount>,
+    /// CHECK: Only the most recent hash is read, see `recent_slot_hash`
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens the challenge of the current day of the season. Anyone can call it,
+/// the seed is only drawn once the day has started.
+pub fn create_daily_challenge(ctx: Context<CreateDailyChallenge>, day: u32) -> Result<()> {
+    let season_account = &ctx.accounts.season_account;
+    check_season_ended(season_account)?;
+
+    let now = Clock::get()?.unix_timestamp as u64;
+    let current_day = (now - season_account.started_at) / DAILY_CHALLENGE_DURATION;
+    require!(day as u64 == current_day, FomoLoveErrorCode::InvalidChallengeDay);
+
+    let slot_hash = recent_slot_hash(&ctx.accounts.slot_hashes)?;
+    let seed = keccak::hashv(&[&slot_hash, &season_account.season_id.to_le_bytes(), &day.to_le_bytes()]).0;
+
+    let started_at = season_account.started_at + current_day * DAILY_CHALLENGE_DURATION;
+
+    let daily_challenge = ctx.accounts.daily_challenge.deref_mut();
+    daily_challenge.bump = ctx.bumps.daily_challenge;
+    daily_challenge.season_id = season_account.season_id;
+    daily_challenge.day = day;
+    daily_challenge.seed = seed;
+    daily_challenge.started_at = started_at;
+    daily_challenge.ended_at = (started_at + DAILY_CHALLENGE_DURATION).min(season_account.ended_at);
+    daily_challenge.total_game_played = 0;
+    let params = &season_account.params;
+    daily_challenge.leaderboard = LeaderBoard::new(GameMode::Classic, params.daily_challenge_size(), params);
+
+    Ok(())
+}
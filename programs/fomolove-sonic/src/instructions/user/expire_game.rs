@@ -0,0 +1,32 @@
+use std::ops::DerefMut;
+
+use anchor_lang::prelude::*;
+
+use crate::{error::FomoLoveErrorCode, GameAccount, GameStatus, SeasonAccount};
+
+#[derive(Accounts)]
+pub struct ExpireGame<'info> {
+    pub signer: Signer<'info>,
+    pub season_account: Account<'info, SeasonAccount>,
+    #[account(mut,
+        constraint = game_account.season_id == season_account.season_id @ FomoLoveErrorCode::SeasonMismatch
+    )]
+    pub game_account: Account<'info, GameAccount>,
+}
+
+/// Closes a game that was never submitted once its season has ended. Anyone
+/// can call it, but not while the season is paused: its end moves back when
+/// it resumes.
+pub fn expire_game(ctx: Context<ExpireGame>) -> Result<()> {
+    let season_account = &ctx.accounts.season_account;
+    let game_account = ctx.accounts.game_account.deref_mut();
+
+    require!(season_account.paused_at == 0, FomoLoveErrorCode::SeasonPaused);
+    let now = Clock::get()?.unix_timestamp as u64;
+    require!(now > season_account.ended_at, FomoLoveErrorCode::SeasonNotEnded);
+
+    game_account.check_not_closed()?;
+    game_account.status = GameStatus::Expired;
+
+    Ok(())
+}
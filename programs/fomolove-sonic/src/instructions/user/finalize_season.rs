@@ -0,0 +1,109 @@
+use std::cmp::Ordering;
+use std::ops::DerefMut;
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::FomoLoveErrorCode, leaderboard_games, GameMode, ModeRanking, SeasonAccount, SeasonLeaderboardAccount,
+    SeasonResultAccount, TeamSeasonAccount, TeamType, GAME_MODE_COUNT,
+};
+
+#[derive(Accounts)]
+pub struct FinalizeSeason<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub season_account: Account<'info, SeasonAccount>,
+    #[account(
+        init,
+        payer = payer,
+        space = SeasonResultAccount::INIT_SPACE,
+        seeds = [b"season_result".as_ref(), &season_account.season_id.to_le_bytes()],
+        bump
+    )]
+    pub season_result: Account<'info, SeasonResultAccount>,
+    /// CHECK: `TeamSeasonAccount` of the meme team, empty when the team never played the season
+    #[account(
+        seeds = [b"team_season".as_ref(), &[TeamType::MemeTeam as u8], &season_account.season_id.to_le_bytes()],
+        bump
+    )]
+    pub meme_team_season: UncheckedAccount<'info>,
+    /// CHECK: `TeamSeasonAccount` of the chain team, empty when the team never played the season
+    #[account(
+        seeds = [b"team_season".as_ref(), &[TeamType::ChainTeam as u8], &season_account.season_id.to_le_bytes()],
+        bump
+    )]
+    pub chain_team_season: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Closes an ended season. Anyone can call it, passing the created
+/// leaderboards of the season as remaining accounts in `GameMode` order.
+/// The leaderboards are frozen, their ranks are the final ones. Only the
+/// header and first game of each board are read and the team totals come
+/// from the `TeamSeasonAccount`s, so the cost does not grow with the boards.
+pub fn finalize_season<'info>(ctx: Context<'_, '_, 'info, 'info, FinalizeSeason<'info>>) -> Result<()> {
+    let season_account = &ctx.accounts.season_account;
+    require!(!season_account.finalized, FomoLoveErrorCode::SeasonFinalized);
+    require!(season_account.paused_at == 0, FomoLoveErrorCode::SeasonPaused);
+    let now = Clock::get()?.unix_timestamp as u64;
+    require!(season_account.ended_at < now, FomoLoveErrorCode::SeasonNotEnded);
+
+    let created = season_account.leaderboards.iter().filter(|key| **key != Pubkey::default());
+    require!(
+        ctx.remaining_accounts.len() == created.count(),
+        FomoLoveErrorCode::LeaderboardMismatch
+    );
+
+    let (meme_team_score, meme_team_games) = team_totals(&ctx.accounts.meme_team_season)?;
+    let (chain_team_score, chain_team_games) = team_totals(&ctx.accounts.chain_team_season)?;
+
+    let season_result = ctx.accounts.season_result.deref_mut();
+    season_result.bump = ctx.bumps.season_result;
+    season_result.season_id = season_account.season_id;
+    season_result.finalized_at = now;
+    season_result.meme_team_score = meme_team_score;
+    season_result.chain_team_score = chain_team_score;
+    season_result.meme_team_games = meme_team_games;
+    season_result.chain_team_games = chain_team_games;
+    season_result.rankings = [ModeRanking::default(); GAME_MODE_COUNT];
+
+    let mut remaining_accounts = ctx.remaining_accounts.iter();
+    for mode in GameMode::ALL {
+        let key = season_account.leaderboards[mode.index()];
+        if key == Pubkey::default() {
+            continue;
+        }
+        let account_info = remaining_accounts.next().ok_or(FomoLoveErrorCode::LeaderboardMismatch)?;
+        require_keys_eq!(account_info.key(), key, FomoLoveErrorCode::LeaderboardMismatch);
+
+        let leaderboard = AccountLoader::<SeasonLeaderboardAccount>::try_from(account_info)?;
+        let games = leaderboard_games(&leaderboard)?;
+        let ranking = &mut season_result.rankings[mode.index()];
+        ranking.leaderboard = key;
+        ranking.game_count = games.len() as u16;
+        if let Some(first) = games.first() {
+            ranking.winner = first.player;
+            ranking.winner_score = first.score;
+        }
+    }
+
+    season_result.winning_team = match season_result.meme_team_score.cmp(&season_result.chain_team_score) {
+        Ordering::Greater => TeamType::MemeTeam,
+        Ordering::Less => TeamType::ChainTeam,
+        Ordering::Equal => TeamType::None,
+    };
+
+    ctx.accounts.season_account.finalized = true;
+
+    Ok(())
+}
+
+/// Total score and games played of a team, zero when it never played.
+fn team_totals(account: &AccountInfo) -> Result<(u64, u64)> {
+    if account.data_is_empty() {
+        return Ok((0, 0));
+    }
+    let team_season = TeamSeasonAccount::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    Ok((team_season.total_score, team_season.game_played))
+}
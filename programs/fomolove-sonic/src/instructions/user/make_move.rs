@@ -1,13 +1,16 @@
 use std::ops::DerefMut;
 
 use crate::{
-    error::FomoLoveErrorCode, ConfigAccount, Direction, GameAccount, TeamAccount, UserAccount,
-    UserSeasonAccount, WinnerAccount, WinnerTopGame,
+    check_season_ended, events::SpawnRandomness, error::FomoLoveErrorCode, update_nft_metadata_field, ConfigAccount,
+    Direction, GameAccount, GameLogAccount, GameStatus, RandomnessMode, RandomnessProvider, SeasonAccount,
+    SlotHashesProvider, TeamAccount, TeamSeasonAccount, UserAccount, UserSeasonAccount,
+    TopGame, WinnerAccount, WinnerTopGame,
 };
 use anchor_lang::prelude::*;
+use fomolove_engine::tile_exponent;
 use anchor_spl::token_2022::Token2022;
 use anchor_spl::token_interface::{Mint, TokenAccount};
-use solana_program::program::invoke_signed;
+use spl_token_metadata_interface::state::Field;
 
 #[derive(Accounts)]
 pub struct MakeMove<'info> {
@@ -15,18 +18,45 @@ pub struct MakeMove<'info> {
     pub user: Signer<'info>,
     #[account(mut)]
     pub config_account: Account<'info, ConfigAccount>,
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"user".as_ref(), user.key().as_ref()],
+        bump
+    )]
     pub user_account: Account<'info, UserAccount>,
     #[account(mut)]
     pub winner_account: Account<'info, WinnerAccount>,
     #[account(mut)]
     pub user_team_account: Account<'info, TeamAccount>,
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"user_season".as_ref(), user.key().as_ref(), &game.season_id.to_le_bytes()],
+        bump
+    )]
     pub user_season_account: Account<'info, UserSeasonAccount>,
+    #[account(
+        mut,
+        seeds = [b"team_season".as_ref(), &[user_account.team as u8], &game.season_id.to_le_bytes()],
+        bump = team_season_account.bump
+    )]
+    pub team_season_account: Account<'info, TeamSeasonAccount>,
     #[account(mut,
         constraint = game.nft_mint.key() == nft_mint.key()
     )]
     pub game: Account<'info, GameAccount>,
+    #[account(
+        constraint = season_account.season_id == game.season_id @ FomoLoveErrorCode::SeasonMismatch
+    )]
+    pub season_account: Account<'info, SeasonAccount>,
+    #[account(
+        mut,
+        seeds = [b"game_log".as_ref(), game.key().as_ref()],
+        bump = game_log_account.bump,
+        realloc = GameLogAccount::space_for(game_log_account.move_count as usize + 1),
+        realloc::payer = user,
+        realloc::zero = false,
+    )]
+    pub game_log_account: Account<'info, GameLogAccount>,
     #[account(mut)]
     pub nft_mint: InterfaceAccount<'info, Mint>,
     #[account(mut,
@@ -35,83 +65,239 @@ pub struct MakeMove<'info> {
     )]
     pub nft_token_account: InterfaceAccount<'info, TokenAccount>,
     pub token_program: Program<'info, Token2022>,
+    /// CHECK: Only the most recent hash is read, see `recent_slot_hash`
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
 pub fn make_move(ctx: Context<MakeMove>, direction: Direction) -> Result<()> {
-    let game = &mut ctx.accounts.game;
-    let config_account = &ctx.accounts.config_account;
-    let user_account = &ctx.accounts.user_account;
-    let user_team_account = &ctx.accounts.user_team_account;
+    let accounts = ctx.accounts;
+    play_moves(
+        MoveAccounts {
+            user: &accounts.user,
+            config_account: &accounts.config_account,
+            user_account: &accounts.user_account,
+            winner_account: &mut accounts.winner_account,
+            user_team_account: &accounts.user_team_account,
+            user_season_account: &mut accounts.user_season_account,
+            team_season_account: &mut accounts.team_season_account,
+            game: &mut accounts.game,
+            season_account: &accounts.season_account,
+            game_log_account: &mut accounts.game_log_account,
+            nft_mint: &accounts.nft_mint,
+            slot_hashes: &accounts.slot_hashes,
+        },
+        &[direction],
+    )?;
+    Ok(())
+}
 
-    let winner_account = ctx.accounts.winner_account.deref_mut();
+/// Accounts shared by `make_move` and `make_moves`.
+pub struct MoveAccounts<'a, 'info> {
+    pub user: &'a Signer<'info>,
+    pub config_account: &'a Account<'info, ConfigAccount>,
+    pub user_account: &'a Account<'info, UserAccount>,
+    pub winner_account: &'a mut Account<'info, WinnerAccount>,
+    pub user_team_account: &'a Account<'info, TeamAccount>,
+    pub user_season_account: &'a mut Account<'info, UserSeasonAccount>,
+    pub team_season_account: &'a mut Account<'info, TeamSeasonAccount>,
+    pub game: &'a mut Account<'info, GameAccount>,
+    pub season_account: &'a Account<'info, SeasonAccount>,
+    pub game_log_account: &'a mut Account<'info, GameLogAccount>,
+    pub nft_mint: &'a InterfaceAccount<'info, Mint>,
+    pub slot_hashes: &'a UncheckedAccount<'info>,
+}
 
-    let user_season_account = ctx.accounts.user_season_account.deref_mut();
+/// Applies `directions` in order and stops at the first move that does not
+/// change the board or leaves the game waiting (game over, pending spawn).
+/// The user season stats, NFT metadata and winner board are updated once at
+/// the end. Returns the number of moves applied.
+pub fn play_moves(accounts: MoveAccounts, directions: &[Direction]) -> Result<u32> {
+    let game_key = accounts.game.key();
+    let user_key = accounts.user.key();
 
-    let old_board = game.board;
+    let game = accounts.game.deref_mut();
+    let game_log = accounts.game_log_account.deref_mut();
+    let user_account = accounts.user_account;
+    let user_team_account = accounts.user_team_account;
 
     let old_top_tile = game.top_tile; // Store the old value of top_tile
+    let old_score = game.score;
+
+    check_season_ended(accounts.season_account)?;
 
     // Check if team types match
     if user_account.team != user_team_account.team_type {
         return Err(FomoLoveErrorCode::TeamTypeMismatch.into());
     }
-    match direction {
-        Direction::Up => game.move_up(),
-        Direction::Down => game.move_down(),
-        Direction::Left => game.move_left(),
-        Direction::Right => game.move_right(),
+    require!(game.status == GameStatus::Active, FomoLoveErrorCode::GameOver);
+    require!(!game.pending_spawn, FomoLoveErrorCode::SpawnPending);
+    let now = Clock::get()?.unix_timestamp as u64;
+    game.catch_up_pauses(accounts.season_account.paused_time);
+    game.check_time_left(now)?;
+
+    // Every spawn of the batch mixes in its own move counter
+    let randomness = match game.randomness_mode {
+        RandomnessMode::SlotHashes => Some(
+            SlotHashesProvider {
+                slot_hashes: accounts.slot_hashes,
+                player: user_key,
+            }
+            .randomness()?,
+        ),
+        RandomnessMode::Oracle => None,
+        RandomnessMode::Challenge => Some(game.seed),
+    };
+    if let (RandomnessMode::SlotHashes, Some(randomness)) = (game.randomness_mode, randomness) {
+        emit!(SpawnRandomness {
+            game: game_key,
+            first_move: game.move_count + 1,
+            randomness,
+        });
     }
 
-    if old_board == game.board {
-        return Err(FomoLoveErrorCode::GameNotChange.into());
+    let mut applied: u32 = 0;
+    let mut merges: u64 = 0;
+    for &direction in directions {
+        let Some(move_merges) = game.apply_move(direction)? else {
+            break;
+        };
+        merges += move_merges as u64;
+
+        game.move_count += 1;
+        game_log.record(direction);
+        applied += 1;
+
+        match &randomness {
+            Some(randomness) => {
+                let mut entropy = game.spawn_entropy(&game_key, randomness);
+                game.add_new_tile(&mut entropy)?;
+            }
+            None => game.pending_spawn = true,
+        }
+
+        // The final move is kept, the game just stops accepting new ones
+        if game.finish_if_over()? || game.pending_spawn {
+            break;
+        }
     }
 
-    if game.is_game_over() {
-        return Err(FomoLoveErrorCode::GameOver.into());
+    if applied == 0 {
+        return Err(FomoLoveErrorCode::GameNotChange.into());
     }
 
-    game.add_new_tile()?;
+    let user_season_account = accounts.user_season_account.deref_mut();
+    user_season_account.total_moves += applied as u64;
+    user_season_account.total_merges += merges;
+    user_season_account.last_played_at = now;
+
+    record_progress(
+        ProgressAccounts {
+            player: user_key,
+            config_account: accounts.config_account,
+            user_account,
+            winner_account: accounts.winner_account,
+            user_team_account,
+            user_season_account: accounts.user_season_account,
+            team_season_account: accounts.team_season_account,
+            game: accounts.game,
+            nft_mint: accounts.nft_mint,
+        },
+        old_top_tile,
+        old_score,
+    )?;
+
+    Ok(applied)
+}
+
+/// Accounts brought up to date after the board of a game changes, by a move
+/// or by an oracle spawn.
+pub struct ProgressAccounts<'a, 'info> {
+    pub player: Pubkey,
+    pub config_account: &'a Account<'info, ConfigAccount>,
+    pub user_account: &'a Account<'info, UserAccount>,
+    pub winner_account: &'a mut Account<'info, WinnerAccount>,
+    pub user_team_account: &'a Account<'info, TeamAccount>,
+    pub user_season_account: &'a mut Account<'info, UserSeasonAccount>,
+    pub team_season_account: &'a mut Account<'info, TeamSeasonAccount>,
+    pub game: &'a Account<'info, GameAccount>,
+    pub nft_mint: &'a InterfaceAccount<'info, Mint>,
+}
+
+/// Updates the season stats, NFT metadata and winner board from a game that
+/// had `old_top_tile` and `old_score` before its board changed.
+pub fn record_progress(accounts: ProgressAccounts, old_top_tile: u32, old_score: u64) -> Result<()> {
+    let game_key = accounts.game.key();
+    let player = accounts.player;
+
+    let game = accounts.game;
+    let config_account = accounts.config_account;
+    let user_account = accounts.user_account;
+    let user_team_account = accounts.user_team_account;
+
+    let winner_account = accounts.winner_account.deref_mut();
+
+    let user_season_account = accounts.user_season_account.deref_mut();
+    let team_season_account = accounts.team_season_account.deref_mut();
 
     // Update the highest score if the current game's score is higher
     if game.score > user_season_account.hightest_score {
         user_season_account.hightest_score = game.score;
     }
+    user_season_account.best_tile = user_season_account.best_tile.max(game.top_tile);
+    if old_top_tile < 2048 && game.top_tile >= 2048 {
+        user_season_account.winning_game_count += 1;
+    }
+
+    // The team totals follow the game as it is played
+    team_season_account.total_score += game.score - old_score;
+    if old_top_tile < 2048 && game.top_tile >= 2048 {
+        team_season_account.winning_game_count += 1;
+    }
+    if game.score > team_season_account.best_game.score {
+        team_season_account.best_game = TopGame {
+            game: game_key,
+            player,
+            team: user_account.team,
+            score: game.score,
+            top_tile: game.top_tile,
+            move_count: game.move_count,
+        };
+    }
 
     // Check if top_tile has been updated
     if game.top_tile != old_top_tile {
-        let seeds = b"config";
-        let bump = ctx.accounts.config_account.bump;
-        let signer: &[&[&[u8]]] = &[&[seeds, &[bump]]];
-
         let base_url = &user_team_account.base_url;
         let suffix = map_top_tile_to_suffix(game.top_tile);
         let new_url = format!("{}{}.png", base_url, suffix);
 
-        invoke_signed(
-            &spl_token_metadata_interface::instruction::update_field(
-                &spl_token_2022::id(),
-                ctx.accounts.nft_mint.to_account_info().key,
-                ctx.accounts.config_account.to_account_info().key,
-                spl_token_metadata_interface::state::Field::Uri,
-                new_url.to_string(),
-            ),
-            &[
-                ctx.accounts.nft_mint.to_account_info().clone(),
-                ctx.accounts.config_account.to_account_info().clone(),
-            ],
-            signer,
+        update_nft_metadata_field(
+            &accounts.nft_mint.to_account_info(),
+            &config_account.to_account_info(),
+            config_account.bump,
+            Field::Uri,
+            new_url,
+        )?;
+    }
+
+    if game.status == GameStatus::Over {
+        update_nft_metadata_field(
+            &accounts.nft_mint.to_account_info(),
+            &config_account.to_account_info(),
+            config_account.bump,
+            Field::Key("game_over".to_string()),
+            "true".to_string(),
         )?;
     }
 
     // Check if top_tile reaches 2048 and update WinnerAccount
     if game.top_tile >= 2048 {
-        let user_pubkey = ctx.accounts.user.key();
         let winner_top_game = WinnerTopGame {
-            user: user_pubkey,
+            user: player,
             team: user_account.team,
-            season_id: config_account.current_season_id,
+            season_id: game.season_id,
             score: game.score,
             top_tile: game.top_tile,
         };
@@ -119,7 +305,7 @@ pub fn make_move(ctx: Context<MakeMove>, direction: Direction) -> Result<()> {
         let user_exists = winner_account
             .leaderboard
             .iter()
-            .position(|entry| entry.user == user_pubkey);
+            .position(|entry| entry.user == player);
 
         match user_exists {
             Some(index) => {
@@ -151,19 +337,23 @@ pub fn make_move(ctx: Context<MakeMove>, direction: Direction) -> Result<()> {
     Ok(())
 }
 
-fn map_top_tile_to_suffix(top_tile: u16) -> String {
-    match top_tile {
-        2 => "1".to_string(),
-        4 => "2".to_string(),
-        8 => "3".to_string(),
-        16 => "4".to_string(),
-        32 => "5".to_string(),
-        64 => "6".to_string(),
-        128 => "7".to_string(),
-        256 => "8".to_string(),
-        512 => "9".to_string(),
-        1024 => "10".to_string(),
-        2048 => "11".to_string(),
-        _ => "1".to_string(),
+/// Image suffix for a tile value: 2 => "1", 2048 => "11", 131072 => "17".
+fn map_top_tile_to_suffix(top_tile: u32) -> String {
+    tile_exponent(top_tile as u64).max(1).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fomolove_engine::{tile_value, MAX_TILE_EXPONENT};
+
+    #[test]
+    fn top_tile_suffix_is_the_exponent() {
+        assert_eq!(map_top_tile_to_suffix(0), "1");
+        assert_eq!(map_top_tile_to_suffix(2), "1");
+        assert_eq!(map_top_tile_to_suffix(2048), "11");
+        assert_eq!(map_top_tile_to_suffix(1 << 16), "16");
+        assert_eq!(map_top_tile_to_suffix(131072), "17");
+        assert_eq!(map_top_tile_to_suffix(tile_value(MAX_TILE_EXPONENT) as u32), "31");
     }
 }
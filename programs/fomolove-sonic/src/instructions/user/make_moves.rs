@@ -0,0 +1,94 @@
+use crate::{
+    error::FomoLoveErrorCode, play_moves, ConfigAccount, Direction, GameAccount, GameLogAccount,
+    MoveAccounts, SeasonAccount, TeamAccount, TeamSeasonAccount, UserAccount, UserSeasonAccount, WinnerAccount,
+    MAX_MOVES_PER_BATCH,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+#[derive(Accounts)]
+#[instruction(directions: Vec<Direction>)]
+pub struct MakeMoves<'info> {
+    // Checked ahead of the game log realloc, which is sized by the batch
+    #[account(
+        mut,
+        constraint = directions.len() <= MAX_MOVES_PER_BATCH as usize @ FomoLoveErrorCode::TooManyMoves
+    )]
+    pub user: Signer<'info>,
+    #[account(mut)]
+    pub config_account: Account<'info, ConfigAccount>,
+    #[account(
+        mut,
+        seeds = [b"user".as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    #[account(mut)]
+    pub winner_account: Account<'info, WinnerAccount>,
+    #[account(mut)]
+    pub user_team_account: Account<'info, TeamAccount>,
+    #[account(
+        mut,
+        seeds = [b"user_season".as_ref(), user.key().as_ref(), &game.season_id.to_le_bytes()],
+        bump
+    )]
+    pub user_season_account: Account<'info, UserSeasonAccount>,
+    #[account(
+        mut,
+        seeds = [b"team_season".as_ref(), &[user_account.team as u8], &game.season_id.to_le_bytes()],
+        bump = team_season_account.bump
+    )]
+    pub team_season_account: Account<'info, TeamSeasonAccount>,
+    #[account(mut,
+        constraint = game.nft_mint.key() == nft_mint.key()
+    )]
+    pub game: Account<'info, GameAccount>,
+    #[account(
+        constraint = season_account.season_id == game.season_id @ FomoLoveErrorCode::SeasonMismatch
+    )]
+    pub season_account: Account<'info, SeasonAccount>,
+    #[account(
+        mut,
+        seeds = [b"game_log".as_ref(), game.key().as_ref()],
+        bump = game_log_account.bump,
+        realloc = GameLogAccount::space_for(game_log_account.move_count as usize + directions.len()),
+        realloc::payer = user,
+        realloc::zero = false,
+    )]
+    pub game_log_account: Account<'info, GameLogAccount>,
+    #[account(mut)]
+    pub nft_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut,
+        token::mint = nft_mint.key(),
+        token::authority = user.key(),
+    )]
+    pub nft_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Program<'info, Token2022>,
+    /// CHECK: Only the most recent hash is read, see `recent_slot_hash`
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn make_moves(ctx: Context<MakeMoves>, directions: Vec<Direction>) -> Result<u32> {
+    let accounts = ctx.accounts;
+    play_moves(
+        MoveAccounts {
+            user: &accounts.user,
+            config_account: &accounts.config_account,
+            user_account: &accounts.user_account,
+            winner_account: &mut accounts.winner_account,
+            user_team_account: &accounts.user_team_account,
+            user_season_account: &mut accounts.user_season_account,
+            team_season_account: &mut accounts.team_season_account,
+            game: &mut accounts.game,
+            season_account: &accounts.season_account,
+            game_log_account: &mut accounts.game_log_account,
+            nft_mint: &accounts.nft_mint,
+            slot_hashes: &accounts.slot_hashes,
+        },
+        &directions,
+    )
+}
@@ -7,5 +7,23 @@ pub use register_game::*;
 pub mod make_move;
 pub use make_move::*;
 
+pub mod make_moves;
+pub use make_moves::*;
+
 pub mod submit_leaderboard;
 pub use submit_leaderboard::*;
+
+pub mod expire_game;
+pub use expire_game::*;
+
+pub mod create_daily_challenge;
+pub use create_daily_challenge::*;
+
+pub mod submit_daily_challenge;
+pub use submit_daily_challenge::*;
+
+pub mod finalize_season;
+pub use finalize_season::*;
+
+pub mod crank_next_season;
+pub use crank_next_season::*;
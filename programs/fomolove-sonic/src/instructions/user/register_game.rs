@@ -7,14 +7,18 @@ use solana_program::program::{invoke, invoke_signed};
 use spl_token_2022::{extension::ExtensionType, state::Mint};
 
 use crate::{
-    check_season_ended, error::{FomoLoveErrorCode, ProgramErrorCode}, ConfigAccount, GameAccount, SeasonAccount, TeamType, UserAccount, UserSeasonAccount
+    check_season_ended, error::{FomoLoveErrorCode, ProgramErrorCode}, events::SpawnRandomness, ConfigAccount, DailyChallengeAccount, GameAccount, GameLogAccount, GameMode, GameStatus, RandomnessMode, RandomnessProvider, SeasonAccount, SlotHashesProvider, TeamSeasonAccount, TeamType, UserAccount, UserSeasonAccount
 };
 
 #[derive(Accounts)]
 pub struct RegisterGame<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"user".as_ref(), user.key().as_ref()],
+        bump
+    )]
     pub user_account: Account<'info, UserAccount>,
     #[account(mut)]
     pub season_account: Account<'info, SeasonAccount>,
@@ -24,18 +28,40 @@ pub struct RegisterGame<'info> {
         init_if_needed,
         payer = user,
         space = UserSeasonAccount::INIT_SPACE,
-        seeds = [b"user_season".as_ref(), &user.key().as_ref(), &[season_account.season_id]],
+        seeds = [b"user_season".as_ref(), &user.key().as_ref(), &season_account.season_id.to_le_bytes()],
         bump
     )]
     pub user_season_account: Account<'info, UserSeasonAccount>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = TeamSeasonAccount::INIT_SPACE,
+        seeds = [b"team_season".as_ref(), &[user_account.team as u8], &season_account.season_id.to_le_bytes()],
+        bump
+    )]
+    pub team_season_account: Box<Account<'info, TeamSeasonAccount>>,
     #[account(
       init_if_needed,
       payer = user,
-      space = GameAccount::INIT_SPACE,
+      space = GameAccount::space_for(season_account.params.board_size),
       seeds = [b"game".as_ref(), &nft_mint.key().as_ref()],
       bump
     )]
     pub game_account: Account<'info, GameAccount>,
+    #[account(
+      init_if_needed,
+      payer = user,
+      space = GameLogAccount::space_for(0),
+      seeds = [b"game_log".as_ref(), &game_account.key().as_ref()],
+      bump
+    )]
+    pub game_log_account: Account<'info, GameLogAccount>,
+    /// Set to register the game for this daily challenge
+    #[account(
+        mut,
+        constraint = daily_challenge.season_id == season_account.season_id @ FomoLoveErrorCode::SeasonMismatch
+    )]
+    pub daily_challenge: Option<Account<'info, DailyChallengeAccount>>,
 
     pub token_program: Program<'info, Token2022>,
     #[account(  
@@ -48,16 +74,38 @@ pub struct RegisterGame<'info> {
 
     pub rent: Sysvar<'info, Rent>,
 
+    /// CHECK: Only the most recent hash is read, see `recent_slot_hash`
+    #[account(address = anchor_lang::solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
     pub associated_token_program: Program<'info, AssociatedToken>,
 
     pub system_program: Program<'info, System>,
 }
 
-pub fn register_game(ctx: Context<RegisterGame>) -> Result<()> {
+pub fn register_game(ctx: Context<RegisterGame>, mode: GameMode) -> Result<()> {
+    let game_key = ctx.accounts.game_account.key();
+    let user_key = ctx.accounts.user.key();
+    let now = Clock::get()?.unix_timestamp;
+    let mut randomness = SlotHashesProvider {
+        slot_hashes: &ctx.accounts.slot_hashes,
+        player: user_key,
+    }
+    .randomness()?;
+
+    let season_info = ctx.accounts.season_account.to_account_info();
+
+    let game_log = ctx.accounts.game_log_account.deref_mut();
+    game_log.bump = ctx.bumps.game_log_account;
+    game_log.game = game_key;
+    game_log.move_count = 0;
+    game_log.moves = Vec::new();
+
     let game = ctx.accounts.game_account.deref_mut();
     let season_account = ctx.accounts.season_account.deref_mut();
     let user_account = ctx.accounts.user_account.deref_mut();
     let user_season_account = ctx.accounts.user_season_account.deref_mut();
+    let team_season_account = ctx.accounts.team_season_account.deref_mut();
 
     check_season_ended(season_account)?;
 
@@ -65,17 +113,95 @@ pub fn register_game(ctx: Context<RegisterGame>) -> Result<()> {
         user_account.team == TeamType::ChainTeam || user_account.team == TeamType::MemeTeam,
         FomoLoveErrorCode::NotChooseTeam
     );
+    let params = season_account.params;
+    require!(params.mode_rules.is_enabled(mode), FomoLoveErrorCode::ModeNotEnabled);
 
-    user_season_account.hightest_score = 0;
+    if params.entry_fee > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.user.to_account_info(),
+                    to: season_info,
+                },
+            ),
+            params.entry_fee,
+        )?;
+        season_account.entry_fees += params.entry_fee;
+    }
+
+    user_season_account.bump = ctx.bumps.user_season_account;
     user_season_account.game_played += 1;
     user_season_account.season_id = season_account.season_id;
+    if user_season_account.first_played_at == 0 {
+        user_season_account.first_played_at = now as u64;
+    }
+    user_season_account.last_played_at = now as u64;
+
+    team_season_account.bump = ctx.bumps.team_season_account;
+    team_season_account.season_id = season_account.season_id;
+    team_season_account.team = user_account.team;
+    team_season_account.game_played += 1;
 
     game.nft_mint = ctx.accounts.nft_mint.key();
-    game.board = [[0; 4]; 4];
+    game.owner = user_key;
+    game.season_id = season_account.season_id;
+    game.board_size = params.board_size;
+    game.board = vec![0; params.board_size as usize * params.board_size as usize];
     game.score = 0;
     game.top_tile = 2;
-    game.add_new_tile()?;
-    game.add_new_tile()?;
+    game.move_count = 0;
+    game.randomness_mode = season_account.randomness_mode;
+    game.pending_spawn = false;
+    game.status = GameStatus::Active;
+    game.seed = anchor_lang::solana_program::keccak::hashv(&[
+        &randomness,
+        game.nft_mint.as_ref(),
+        &now.to_le_bytes(),
+    ])
+    .0;
+
+    // Only the limit of the chosen mode is kept on the game
+    let rules = params.mode_rules;
+    game.mode = mode;
+    game.ends_at = if mode == GameMode::TimeAttack { now as u64 + rules.time_limit } else { 0 };
+    game.paused_time = season_account.paused_time;
+    game.move_limit = if mode == GameMode::MoveLimited { rules.move_limit } else { 0 };
+    game.target_tile = if mode == GameMode::Sprint { rules.sprint_target_tile } else { 0 };
+    game.special_tiles = params.special_tiles;
+    game.daily_challenge = Pubkey::default();
+    game.scoring = params.scoring;
+    game.streak = 0;
+    game.best_streak = 0;
+    game.best_combo = 0;
+
+    // Challenge games swap the season randomness for the seed of the day
+    if let Some(daily_challenge) = ctx.accounts.daily_challenge.as_mut() {
+        require!(mode == GameMode::Classic, FomoLoveErrorCode::InvalidChallengeMode);
+        require!(
+            daily_challenge.started_at <= now as u64 && (now as u64) < daily_challenge.ended_at,
+            FomoLoveErrorCode::ChallengeClosed
+        );
+
+        daily_challenge.total_game_played += 1;
+        game.randomness_mode = RandomnessMode::Challenge;
+        game.seed = daily_challenge.seed;
+        game.daily_challenge = daily_challenge.key();
+        randomness = daily_challenge.seed;
+    }
+
+    // The opening tiles are drawn from SlotHashes or the challenge seed,
+    // oracle seasons only defer the spawns that follow a move
+    if game.randomness_mode != RandomnessMode::Challenge {
+        emit!(SpawnRandomness {
+            game: game_key,
+            first_move: 0,
+            randomness,
+        });
+    }
+    let mut entropy = game.spawn_entropy(&game_key, &randomness);
+    game.add_new_tile(&mut entropy)?;
+    game.add_new_tile(&mut entropy)?;
 
     // Extract season_id before passing to the function to avoid borrowing issues
     let season_id = season_account.season_id;
@@ -85,7 +211,7 @@ pub fn register_game(ctx: Context<RegisterGame>) -> Result<()> {
     Ok(())
 }
 
-pub fn initialize_mint_and_metadata(ctx: &Context<RegisterGame>, season_id: u8) -> Result<()> {
+pub fn initialize_mint_and_metadata(ctx: &Context<RegisterGame>, season_id: u32) -> Result<()> {
     let space = match ExtensionType::try_calculate_account_len::<Mint>(&[ExtensionType::MetadataPointer]) {
         Ok(space) => space,
         Err(_) => return err!(ProgramErrorCode::InvalidMintAccountSpace),
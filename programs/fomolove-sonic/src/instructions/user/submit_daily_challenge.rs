@@ -0,0 +1,71 @@
+use std::ops::DerefMut;
+
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::Token2022;
+
+use crate::{
+    check_season_ended, error::FomoLoveErrorCode, update_nft_metadata_field, ConfigAccount,
+    DailyChallengeAccount, GameAccount, GameStatus, SeasonAccount, TopGame, UserAccount,
+};
+use spl_token_metadata_interface::state::Field;
+
+#[derive(Accounts)]
+pub struct SubmitDailyChallenge<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [b"user".as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    pub config_account: Account<'info, ConfigAccount>,
+    pub season_account: Account<'info, SeasonAccount>,
+    #[account(
+        mut,
+        constraint = daily_challenge.season_id == season_account.season_id @ FomoLoveErrorCode::SeasonMismatch
+    )]
+    pub daily_challenge: Account<'info, DailyChallengeAccount>,
+    #[account(mut,
+        constraint = game_account.owner == user.key() @ FomoLoveErrorCode::Unauthorized,
+        constraint = game_account.daily_challenge == daily_challenge.key() @ FomoLoveErrorCode::ChallengeMismatch,
+        constraint = game_account.nft_mint == nft_mint.key()
+    )]
+    pub game_account: Account<'info, GameAccount>,
+    /// CHECK: Make sure the ata to the mint is actually owned by the signer
+    #[account(mut)]
+    pub nft_mint: AccountInfo<'info>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Records a daily challenge game on the leaderboard of its day. Games of a
+/// day can be submitted until the season ends.
+pub fn submit_daily_challenge(ctx: Context<SubmitDailyChallenge>) -> Result<()> {
+    let game_account = &ctx.accounts.game_account;
+
+    check_season_ended(&ctx.accounts.season_account)?;
+    game_account.check_not_closed()?;
+
+    let top_game = TopGame {
+        game: game_account.key(),
+        player: ctx.accounts.user.key(),
+        team: ctx.accounts.user_account.team,
+        score: game_account.score,
+        top_tile: game_account.top_tile,
+        move_count: game_account.move_count,
+    };
+
+    let daily_challenge = ctx.accounts.daily_challenge.deref_mut();
+    daily_challenge.leaderboard.submit(top_game, game_account.target_tile)?;
+
+    ctx.accounts.game_account.status = GameStatus::Submitted;
+
+    update_nft_metadata_field(
+        &ctx.accounts.nft_mint,
+        &ctx.accounts.config_account.to_account_info(),
+        ctx.accounts.config_account.bump,
+        Field::Key("game_over".to_string()),
+        "true".to_string(),
+    )?;
+    Ok(())
+}
@@ -1,23 +1,57 @@
-use std::ops::DerefMut;
-
 use anchor_lang::prelude::*;
 use anchor_spl::token_2022::Token2022;
 
-use crate::{check_season_ended, error::FomoLoveErrorCode, ConfigAccount, GameAccount, LeaderBoard, SeasonAccount, TopGame, UserAccount};
-use solana_program::program::invoke_signed;
+use crate::{
+    check_season_ended, error::FomoLoveErrorCode, update_nft_metadata_field, ConfigAccount, GameAccount, GameStatus,
+    LeaderboardPlayerAccount, RandomnessMode, SeasonAccount, SeasonLeaderboard, SeasonLeaderboardAccount, TeamSeasonAccount, TopGame,
+    UserAccount, UserSeasonAccount,
+};
+use spl_token_metadata_interface::state::Field;
 
 #[derive(Accounts)]
 pub struct SubmitLeaderboard<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
-    #[account(mut)]
+    #[account(
+        mut,
+        seeds = [b"user".as_ref(), user.key().as_ref()],
+        bump
+    )]
     pub user_account: Account<'info, UserAccount>,
     #[account(mut)]
     pub config_account: Account<'info, ConfigAccount>,
-    #[account(mut)]
     pub season_account: Account<'info, SeasonAccount>,
-    #[account(mut)]
+    #[account(mut,
+        constraint = game_account.owner == user.key() @ FomoLoveErrorCode::Unauthorized,
+        constraint = game_account.season_id == season_account.season_id @ FomoLoveErrorCode::SeasonMismatch,
+        constraint = game_account.nft_mint == nft_mint.key()
+    )]
     pub game_account: Account<'info, GameAccount>,
+    #[account(
+        mut,
+        address = season_account.leaderboards[game_account.mode.index()] @ FomoLoveErrorCode::LeaderboardMismatch
+    )]
+    pub leaderboard: AccountLoader<'info, SeasonLeaderboardAccount>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = LeaderboardPlayerAccount::INIT_SPACE,
+        seeds = [b"leaderboard_player".as_ref(), leaderboard.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub leaderboard_player: Account<'info, LeaderboardPlayerAccount>,
+    #[account(
+        mut,
+        seeds = [b"team_season".as_ref(), &[user_account.team as u8], &game_account.season_id.to_le_bytes()],
+        bump = team_season_account.bump
+    )]
+    pub team_season_account: Account<'info, TeamSeasonAccount>,
+    #[account(
+        mut,
+        seeds = [b"user_season".as_ref(), user.key().as_ref(), &game_account.season_id.to_le_bytes()],
+        bump
+    )]
+    pub user_season_account: Account<'info, UserSeasonAccount>,
     /// CHECK: Make sure the ata to the mint is actually owned by the signer
     #[account(mut)]
     pub nft_mint: AccountInfo<'info>,
@@ -27,83 +61,45 @@ pub struct SubmitLeaderboard<'info> {
 
 pub fn submit_leaderboard(ctx: Context<SubmitLeaderboard>) -> Result<()> {
 
-    let season_account = ctx.accounts.season_account.deref_mut();
-
     let game_account = &ctx.accounts.game_account;
 
-    check_season_ended(season_account)?;
-
-    let season_leaderboard = &mut season_account.leaderboard;
-
-    require!(
-        game_account.top_tile >= season_leaderboard.min_tile,
-        FomoLoveErrorCode::LowTile
-    );
+    check_season_ended(&ctx.accounts.season_account)?;
+    game_account.check_not_closed()?;
     require!(
-        game_account.score > season_leaderboard.min_score,
-        FomoLoveErrorCode::LowScore
+        game_account.randomness_mode != RandomnessMode::Challenge,
+        FomoLoveErrorCode::DailyChallengeGame
     );
 
+    let mut season_leaderboard = SeasonLeaderboard::load_mut(&ctx.accounts.leaderboard)?;
+
     let top_game = TopGame {
         game: game_account.key(),
         player: ctx.accounts.user.key(),
         team: ctx.accounts.user_account.team,
         score: game_account.score,
         top_tile: game_account.top_tile,
+        move_count: game_account.move_count,
     };
 
-    update_leaderboard(season_leaderboard, top_game, season_leaderboard.max_game_count as usize)?;
-
-    reset_game_account(&mut ctx.accounts.game_account);
-
-    let seeds = b"config";
-    let bump = ctx.accounts.config_account.bump;
-    let signer: &[&[&[u8]]] = &[&[seeds, &[bump]]];
-
-    invoke_signed(
-        &spl_token_metadata_interface::instruction::update_field(
-            &spl_token_2022::id(),
-            ctx.accounts.nft_mint.to_account_info().key,
-            ctx.accounts.config_account.to_account_info().key,
-            spl_token_metadata_interface::state::Field::Key("game_over".to_string()),
-            "true".to_string(),
-        ),
-        &[
-            ctx.accounts.nft_mint.to_account_info().clone(),
-            ctx.accounts.config_account.to_account_info().clone(),
-        ],
-        signer,
+    let leaderboard_player = &mut ctx.accounts.leaderboard_player;
+    leaderboard_player.bump = ctx.bumps.leaderboard_player;
+    leaderboard_player.leaderboard = ctx.accounts.leaderboard.key();
+    leaderboard_player.player = ctx.accounts.user.key();
+
+    season_leaderboard.submit(top_game, game_account.target_tile, &mut leaderboard_player.entries)?;
+    drop(season_leaderboard);
+    ctx.accounts.team_season_account.submitted_game_count += 1;
+    ctx.accounts.user_season_account.submitted_game_count += 1;
+
+    // The board is kept as submitted, the status alone closes the game
+    ctx.accounts.game_account.status = GameStatus::Submitted;
+
+    update_nft_metadata_field(
+        &ctx.accounts.nft_mint,
+        &ctx.accounts.config_account.to_account_info(),
+        ctx.accounts.config_account.bump,
+        Field::Key("game_over".to_string()),
+        "true".to_string(),
     )?;
     Ok(())
 }
-
-fn update_leaderboard(leaderboard: &mut LeaderBoard, top_game: TopGame, max_game_count: usize) -> Result<()> {
-    let top_games = &mut leaderboard.top_games;
-
-    top_games.push(top_game);
-
-    top_games.sort_by(|a, b| {
-        b.score.cmp(&a.score).then_with(|| b.top_tile.cmp(&a.top_tile))
-    });
-
-    // Limit quantity of games in leaderboard
-    if top_games.len() > max_game_count {
-        top_games.pop();
-    }
-    
-    // Update min_score and min_tile if the leaderboard is not empty
-    if top_games.len() == max_game_count {
-        if let Some(bottom_game) = top_games.last() {
-            leaderboard.min_score = bottom_game.score;
-            leaderboard.min_tile = bottom_game.top_tile;
-        }
-    }
-
-    Ok(())
-}
-
-fn reset_game_account(game_account: &mut GameAccount) {
-    game_account.board = [[0; 4]; 4];
-    game_account.score = 0;
-    game_account.top_tile = 2;
-}
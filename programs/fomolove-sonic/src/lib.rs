@@ -5,6 +5,8 @@ pub mod states;
 pub mod error;
 pub mod utils;
 pub mod constant;
+pub mod events;
+pub mod vrf;
 
 use instructions::*;
 use states::*;
@@ -28,9 +30,59 @@ pub mod fomolove_sonic {
         Ok(())
     }
 
+    pub fn update_randomness_provider(ctx: Context<UpdateRandomnessProvider>, randomness_mode: RandomnessMode, randomness_oracle: Pubkey) -> Result<()> {
+        instructions::update_randomness_provider(ctx, randomness_mode, randomness_oracle)?;
+        Ok(())
+    }
+
+    pub fn create_season_leaderboard(ctx: Context<CreateSeasonLeaderboard>, mode: GameMode) -> Result<()> {
+        instructions::create_season_leaderboard(ctx, mode)?;
+        Ok(())
+    }
+
+    pub fn migrate_season_ids(ctx: Context<MigrateSeasonIds>) -> Result<()> {
+        instructions::migrate_season_ids(ctx)?;
+        Ok(())
+    }
+
+    pub fn migrate_season(ctx: Context<MigrateSeason>, legacy_season_id: u8) -> Result<()> {
+        instructions::migrate_season(ctx, legacy_season_id)?;
+        Ok(())
+    }
+
+    pub fn schedule_seasons(ctx: Context<ScheduleSeasons>, seasons: Vec<ScheduledSeason>, crank_reward: u64) -> Result<()> {
+        instructions::schedule_seasons(ctx, seasons, crank_reward)?;
+        Ok(())
+    }
+
+    pub fn unschedule_season(ctx: Context<UnscheduleSeason>, index: u8) -> Result<()> {
+        instructions::unschedule_season(ctx, index)?;
+        Ok(())
+    }
+
+    pub fn pause_season(ctx: Context<PauseSeason>) -> Result<()> {
+        instructions::pause_season(ctx)?;
+        Ok(())
+    }
+
+    pub fn resume_season(ctx: Context<ResumeSeason>) -> Result<()> {
+        instructions::resume_season(ctx)?;
+        Ok(())
+    }
+
+    pub fn update_season_end(ctx: Context<UpdateSeasonEnd>, ended_at: u64) -> Result<()> {
+        instructions::update_season_end(ctx, ended_at)?;
+        Ok(())
+    }
+
+    pub fn withdraw_entry_fees(ctx: Context<WithdrawEntryFees>) -> Result<()> {
+        instructions::withdraw_entry_fees(ctx)?;
+        Ok(())
+    }
+
     /* USER FUNCTION */
-    pub fn start_season(ctx: Context<StartSeason>, start_time: u64) -> Result<()> {
-        instructions::start_season(ctx,start_time)?;
+    pub fn start_season(ctx: Context<StartSeason>, start_time: u64, params: SeasonParams) -> Result<()> {
+        instructions::start_season(ctx, start_time, params)?;
         Ok(())
     }
 
@@ -39,8 +91,8 @@ pub mod fomolove_sonic {
         Ok(())
     }
 
-    pub fn register_game(ctx: Context<RegisterGame>) -> Result<()> {
-        instructions::register_game(ctx)?;
+    pub fn register_game(ctx: Context<RegisterGame>, mode: GameMode) -> Result<()> {
+        instructions::register_game(ctx, mode)?;
         Ok(())
     }
 
@@ -49,8 +101,43 @@ pub mod fomolove_sonic {
         Ok(())
     }
 
+    pub fn make_moves(ctx: Context<MakeMoves>, directions: Vec<Direction>) -> Result<u32> {
+        instructions::make_moves(ctx, directions)
+    }
+
     pub fn submit_leaderboard(ctx: Context<SubmitLeaderboard>) -> Result<()> {
         instructions::submit_leaderboard(ctx)?;
         Ok(())
     }
+
+    pub fn expire_game(ctx: Context<ExpireGame>) -> Result<()> {
+        instructions::expire_game(ctx)?;
+        Ok(())
+    }
+
+    pub fn create_daily_challenge(ctx: Context<CreateDailyChallenge>, day: u32) -> Result<()> {
+        instructions::create_daily_challenge(ctx, day)?;
+        Ok(())
+    }
+
+    pub fn submit_daily_challenge(ctx: Context<SubmitDailyChallenge>) -> Result<()> {
+        instructions::submit_daily_challenge(ctx)?;
+        Ok(())
+    }
+
+    pub fn finalize_season<'info>(ctx: Context<'_, '_, 'info, 'info, FinalizeSeason<'info>>) -> Result<()> {
+        instructions::finalize_season(ctx)?;
+        Ok(())
+    }
+
+    pub fn crank_next_season<'info>(ctx: Context<'_, '_, 'info, 'info, CrankNextSeason<'info>>) -> Result<()> {
+        instructions::crank_next_season(ctx)?;
+        Ok(())
+    }
+
+    /* ORACLE FUNCTION */
+    pub fn fulfill_spawn(ctx: Context<FulfillSpawn>, proof: [u8; 80]) -> Result<()> {
+        instructions::fulfill_spawn(ctx, proof)?;
+        Ok(())
+    }
 }
@@ -1,13 +1,19 @@
 use anchor_lang::prelude::*;
 
+use crate::RandomnessMode;
+
 #[account]
 pub struct ConfigAccount {
   pub bump: u8,
   pub is_initialized: bool,
   pub maintainer: Pubkey,
-  pub current_season_id: u8,
+  pub current_season_id: u32,
+  /// `u64::MAX` while the current season is paused, no season opens before
+  /// it resumes
   pub current_season_ended_at: u64,
-  pub season_duration: u64
+  pub season_duration: u64,
+  pub randomness_mode: RandomnessMode,
+  pub randomness_oracle: Pubkey,
 }
 
 impl Space for ConfigAccount {
@@ -15,7 +21,9 @@ impl Space for ConfigAccount {
         + 1 // bump
         + 1 //is_initialized
         + 32 // maintainer
-        + 1 //current_season_id
+        + 4 //current_season_id
         + 8 // current_season_ended_at
-        + 8; //season_duration
+        + 8 //season_duration
+        + 1 // randomness_mode
+        + 32; // randomness_oracle
 }
\ No newline at end of file
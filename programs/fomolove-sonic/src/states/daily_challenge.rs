@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{LeaderBoard, TopGame};
+
+/// Challenge of one day of a season. Every game registered against it spawns
+/// its tiles from `seed` alone, so identical moves meet identical tiles.
+#[account]
+pub struct DailyChallengeAccount {
+    pub bump: u8,
+    pub season_id: u32,
+    /// Days since the start of the season
+    pub day: u32,
+    pub seed: [u8; 32],
+    pub started_at: u64,
+    pub ended_at: u64,
+    pub total_game_played: u64,
+    /// Classic leaderboard of the day
+    pub leaderboard: LeaderBoard,
+}
+
+impl Space for DailyChallengeAccount {
+    const INIT_SPACE: usize = 8 // Account discriminator added by Anchor for each account
+        + 1 // bump
+        + 4 // season_id
+        + 4 // day
+        + 32 // seed
+        + 8 // started_at
+        + 8 // ended_at
+        + 8 // total_game_played
+        + LeaderBoard::SPACE; // leaderboard
+}
+
+impl DailyChallengeAccount {
+    /// Account size for a leaderboard of `leaderboard_size` games.
+    pub fn space_for(leaderboard_size: u8) -> usize {
+        Self::INIT_SPACE + leaderboard_size as usize * TopGame::SPACE
+    }
+}
@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use fomolove_engine::{cell_exponent, next_streak, tile_value, Board, Entropy, ScoreBonuses, SPECIAL_CHANCE_SCALE};
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
+use crate::{error::FomoLoveErrorCode, GameMode, RandomnessMode, ScoringRules, SpecialTileRules};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
 pub enum Direction {
     Up,
     Down,
@@ -8,178 +12,253 @@ pub enum Direction {
     Right,
 }
 
+impl From<Direction> for fomolove_engine::Direction {
+    fn from(direction: Direction) -> Self {
+        match direction {
+            Direction::Up => fomolove_engine::Direction::Up,
+            Direction::Down => fomolove_engine::Direction::Down,
+            Direction::Left => fomolove_engine::Direction::Left,
+            Direction::Right => fomolove_engine::Direction::Right,
+        }
+    }
+}
+
+impl From<fomolove_engine::Direction> for Direction {
+    fn from(direction: fomolove_engine::Direction) -> Self {
+        match direction {
+            fomolove_engine::Direction::Up => Direction::Up,
+            fomolove_engine::Direction::Down => Direction::Down,
+            fomolove_engine::Direction::Left => Direction::Left,
+            fomolove_engine::Direction::Right => Direction::Right,
+        }
+    }
+}
+
+/// Spawn entropy mixed from the provider randomness, the game PDA, the game
+/// seed and the move counter. It is only as unpredictable as the provider:
+/// a slot hash is known before the move lands, so SlotHashes spawns can be
+/// simulated by the player, while Oracle spawns are revealed after the move.
+pub struct SpawnEntropy {
+    digest: [u8; 32],
+    cursor: usize,
+}
+
+impl SpawnEntropy {
+    pub fn new(randomness: &[u8; 32], game: &Pubkey, seed: &[u8; 32], move_count: u32) -> Self {
+        let digest = keccak::hashv(&[randomness, game.as_ref(), seed, &move_count.to_le_bytes()]).0;
+
+        Self { digest, cursor: 0 }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        if self.cursor + 8 > self.digest.len() {
+            self.digest = keccak::hash(&self.digest).0;
+            self.cursor = 0;
+        }
+
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(&self.digest[self.cursor..self.cursor + 8]);
+        self.cursor += 8;
+        u64::from_le_bytes(bytes)
+    }
+}
+
+impl Entropy for SpawnEntropy {
+    fn pick_cell(&mut self, _board: &Board, empty_count: usize) -> usize {
+        (self.next_u64() % empty_count as u64) as usize
+    }
+
+    fn spawn_four(&mut self, _cell_index: usize) -> bool {
+        self.next_u64() % 10 == 0
+    }
+
+    fn special_roll(&mut self, _cell_index: usize) -> u16 {
+        (self.next_u64() % SPECIAL_CHANCE_SCALE as u64) as u16
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameStatus {
+    Active,
+    /// No legal move is left, the final move has been recorded.
+    Over,
+    /// Recorded on the leaderboard of its season, terminal.
+    Submitted,
+    /// Its season ended before it was submitted, terminal.
+    Expired,
+}
+
 #[account]
 pub struct GameAccount {
     pub nft_mint: Pubkey,
-    pub board: [[u16; 4]; 4],
-    pub score: u32,
-    pub top_tile: u16, // Updated to u16
+    pub owner: Pubkey,
+    pub season_id: u32,
+    pub board_size: u8,
+    /// Row-major cells, tile exponents or special tiles, see `fomolove_engine::Board`
+    pub board: Vec<u8>,
+    pub score: u64,
+    /// Real value of the largest tile reached
+    pub top_tile: u32,
+    pub seed: [u8; 32],
+    pub move_count: u32,
+    pub randomness_mode: RandomnessMode,
+    pub pending_spawn: bool,
+    pub status: GameStatus,
+    pub mode: GameMode,
+    /// TimeAttack deadline as a unix timestamp, 0 in other modes
+    pub ends_at: u64,
+    /// `SeasonAccount::paused_time` the deadline already accounts for
+    pub paused_time: u64,
+    /// MoveLimited move budget, 0 in other modes
+    pub move_limit: u32,
+    /// Real value of the Sprint target tile, 0 in other modes
+    pub target_tile: u32,
+    pub special_tiles: SpecialTileRules,
+    /// Daily challenge the game plays, default for season games
+    pub daily_challenge: Pubkey,
+    pub scoring: ScoringRules,
+    /// Consecutive moves that merged, up to the last one
+    pub streak: u32,
+    pub best_streak: u32,
+    /// Most merges made in a single move
+    pub best_combo: u8,
+}
+
+impl Space for GameAccount {
+    const INIT_SPACE: usize = 8 // Account discriminator added by Anchor for each account
+        + 32 // nft_mint
+        + 32 // owner
+        + 4 // season_id
+        + 1 // board_size
+        + 4 // length of the board vector
+        + 8 // score
+        + 4 // top_tile
+        + 32 // seed
+        + 4 // move_count
+        + 1 // randomness_mode
+        + 1 // pending_spawn
+        + 1 // status
+        + 1 // mode
+        + 8 // ends_at
+        + 8 // paused_time
+        + 4 // move_limit
+        + 4 // target_tile
+        + SpecialTileRules::SPACE // special_tiles
+        + 32 // daily_challenge
+        + ScoringRules::SPACE // scoring
+        + 4 // streak
+        + 4 // best_streak
+        + 1; // best_combo
 }
 
 impl GameAccount {
-    pub const INIT_SPACE: usize = 8 + 32 + 4 * 4 * 2 + 4 + 2; // Updated size calculation
-
-    fn slide_and_merge(row: &mut [u16; 4]) -> (u32, u16) {
-        let mut new_row = [0u16; 4];
-        let mut pos = 0;
-        for &tile in row.iter() {
-            if tile != 0 {
-                new_row[pos] = tile;
-                pos += 1;
-            }
-        }
+    /// Account size for a `board_size` x `board_size` board.
+    pub fn space_for(board_size: u8) -> usize {
+        Self::INIT_SPACE + board_size as usize * board_size as usize
+    }
 
-        let mut score_increment = 0;
-        let mut max_tile = 0;
-        for i in 0..3 {
-            if new_row[i] == new_row[i + 1] && new_row[i] != 0 {
-                if let Some(new_tile) = new_row[i].checked_mul(2) {
-                    new_row[i] = new_tile;
-                    new_row[i + 1] = 0;
-                    score_increment += new_tile as u32;
-
-                    if new_tile > max_tile {
-                        max_tile = new_tile;
-                    }
-                } else {
-                    return (score_increment, max_tile);
-                }
-            }
-        }
+    /// Entropy of the spawn that follows the current move. Challenge games
+    /// leave the game PDA out so every game of the day draws the same tiles.
+    pub fn spawn_entropy(&self, game_key: &Pubkey, randomness: &[u8; 32]) -> SpawnEntropy {
+        let key = match self.randomness_mode {
+            RandomnessMode::Challenge => &self.daily_challenge,
+            RandomnessMode::SlotHashes | RandomnessMode::Oracle => game_key,
+        };
+        SpawnEntropy::new(randomness, key, &self.seed, self.move_count)
+    }
 
-        let mut final_row = [0u16; 4];
-        pos = 0;
-        for &tile in new_row.iter() {
-            if tile != 0 {
-                final_row[pos] = tile;
-                pos += 1;
-            }
+    pub fn engine_board(&self) -> Result<Board> {
+        Board::from_cells(self.board_size as usize, &self.board)
+            .ok_or_else(|| error!(FomoLoveErrorCode::InvalidBoardSize))
+    }
+
+    /// Applies a move and returns its number of merges, `None` when the board
+    /// did not change. The season combo and streak bonuses are added to the
+    /// merge score.
+    pub fn apply_move(&mut self, direction: Direction) -> Result<Option<u8>> {
+        let mut board = self.engine_board()?;
+        let outcome = board.apply_move(direction.into());
+        if !outcome.moved {
+            return Ok(None);
         }
 
-        *row = final_row;
+        let bonuses = ScoreBonuses::from(self.scoring);
+        self.board.copy_from_slice(board.cells());
+        self.score += bonuses.move_score(outcome.score, outcome.merges, self.streak);
+        self.raise_top_tile(outcome.max_tile);
+
+        self.streak = next_streak(self.streak, outcome.merges);
+        self.best_streak = self.best_streak.max(self.streak);
+        self.best_combo = self.best_combo.max(outcome.merges);
 
-        (score_increment, max_tile)
+        Ok(Some(outcome.merges))
     }
 
-    pub fn move_left(&mut self) {
-        let mut total_score_increment = 0;
-        let mut max_tile = 0;
-        for row in self.board.iter_mut() {
-            let (score_increment, row_max_tile) = Self::slide_and_merge(row);
-            total_score_increment += score_increment;
-            if row_max_tile > max_tile {
-                max_tile = row_max_tile;
-            }
-        }
-        self.score += total_score_increment;
-        if max_tile > self.top_tile {
-            self.top_tile = max_tile;
+    pub fn add_new_tile<E: Entropy>(&mut self, entropy: &mut E) -> Result<()> {
+        let mut board = self.engine_board()?;
+
+        if let Some(new_tile) = board.spawn_tile(entropy, &self.special_tiles.into()) {
+            self.board.copy_from_slice(board.cells());
+            self.raise_top_tile(cell_exponent(new_tile));
         }
+
+        Ok(())
     }
 
-    pub fn move_right(&mut self) {
-        for row in self.board.iter_mut() {
-            row.reverse();
-            let (score_increment, max_tile) = Self::slide_and_merge(row);
-            self.score += score_increment;
-            row.reverse();
-            if max_tile > self.top_tile {
-                self.top_tile = max_tile;
-            }
+    /// `MAX_TILE_EXPONENT` keeps every tile value within the `u32` top tile.
+    fn raise_top_tile(&mut self, exponent: u8) {
+        let value = tile_value(exponent) as u32;
+        if value > self.top_tile {
+            self.top_tile = value;
         }
     }
 
-    pub fn move_up(&mut self) {
-        for col in 0..4 {
-            let mut column = [0u16; 4];
-            for row in 0..4 {
-                column[row] = self.board[row][col];
-            }
-            let (score_increment, max_tile) = Self::slide_and_merge(&mut column);
-            self.score += score_increment;
-            for row in 0..4 {
-                self.board[row][col] = column[row];
-            }
-            if max_tile > self.top_tile {
-                self.top_tile = max_tile;
-            }
-        }
+    pub fn is_game_over(&self) -> Result<bool> {
+        Ok(self.engine_board()?.is_game_over())
     }
 
-    pub fn move_down(&mut self) {
-        for col in 0..4 {
-            let mut column = [0u16; 4];
-            for row in 0..4 {
-                column[row] = self.board[row][col];
-            }
-            column.reverse();
-            let (score_increment, max_tile) = Self::slide_and_merge(&mut column);
-            self.score += score_increment;
-            column.reverse();
-            for row in 0..4 {
-                self.board[row][col] = column[row];
-            }
-            if max_tile > self.top_tile {
-                self.top_tile = max_tile;
-            }
+    /// Games can be submitted or expired once, whether finished or not.
+    pub fn check_not_closed(&self) -> Result<()> {
+        match self.status {
+            GameStatus::Active | GameStatus::Over => Ok(()),
+            GameStatus::Submitted => err!(FomoLoveErrorCode::GameAlreadySubmitted),
+            GameStatus::Expired => err!(FomoLoveErrorCode::GameExpired),
         }
     }
 
-    pub fn add_new_tile(&mut self) -> Result<()> {
-        let mut empty_tiles = vec![];
-
-        for i in 0..4 {
-            for j in 0..4 {
-                if self.board[i][j] == 0 {
-                    empty_tiles.push((i, j));
-                }
-            }
+    /// Whether the move or tile goal of the game mode has been reached.
+    pub fn mode_limit_reached(&self) -> bool {
+        match self.mode {
+            GameMode::MoveLimited => self.move_count >= self.move_limit,
+            GameMode::Sprint => self.top_tile >= self.target_tile,
+            GameMode::Classic | GameMode::TimeAttack => false,
         }
+    }
 
-        if empty_tiles.is_empty() {
-            return Ok(());
+    /// Pushes the TimeAttack deadline back by the time the season was paused
+    /// since the game last caught up with `season_paused_time`.
+    pub fn catch_up_pauses(&mut self, season_paused_time: u64) {
+        if self.mode == GameMode::TimeAttack {
+            self.ends_at += season_paused_time.saturating_sub(self.paused_time);
         }
+        self.paused_time = season_paused_time;
+    }
 
-        // Convert the board to a vector of u8 for hashing
-        let board_as_u8: Vec<u8> = self
-            .board
-            .iter()
-            .flat_map(|row| row.iter().flat_map(|&val| val.to_le_bytes()))
-            .collect();
-
-        let rand_index = anchor_lang::solana_program::keccak::hash(&board_as_u8).0[0] as usize
-            % empty_tiles.len();
-        let (x, y) = empty_tiles[rand_index];
-
-        let new_tile =
-            if anchor_lang::solana_program::keccak::hash(&[rand_index as u8]).0[0] % 10 == 0 {
-                4
-            } else {
-                2
-            };
-        self.board[x][y] = new_tile;
-
-        if new_tile > self.top_tile {
-            self.top_tile = new_tile;
+    /// TimeAttack games take no move once their deadline has passed.
+    pub fn check_time_left(&self, now: u64) -> Result<()> {
+        if self.mode == GameMode::TimeAttack {
+            require!(now < self.ends_at, FomoLoveErrorCode::TimeUp);
         }
-
         Ok(())
     }
 
-    pub fn is_game_over(&self) -> bool {
-        for i in 0..4 {
-            for j in 0..4 {
-                if self.board[i][j] == 0 {
-                    return false;
-                }
-                if j < 3 && self.board[i][j] == self.board[i][j + 1] {
-                    return false;
-                }
-                if i < 3 && self.board[i][j] == self.board[i + 1][j] {
-                    return false;
-                }
-            }
+    /// Marks the game as over when no move is left or its mode limit is
+    /// reached, returns whether it ended.
+    pub fn finish_if_over(&mut self) -> Result<bool> {
+        if self.status == GameStatus::Active && (self.mode_limit_reached() || self.is_game_over()?) {
+            self.status = GameStatus::Over;
+            return Ok(true);
         }
-        true
+        Ok(false)
     }
 }
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use fomolove_engine::{move_log_len, write_move};
+
+use crate::Direction;
+
+/// Transcript of every move applied to a game, used to replay disputed scores.
+#[account]
+pub struct GameLogAccount {
+    pub bump: u8,
+    pub game: Pubkey,
+    pub move_count: u32,
+    /// Directions packed 2 bits per move, see `fomolove_engine::move_log`.
+    pub moves: Vec<u8>,
+}
+
+impl Space for GameLogAccount {
+    const INIT_SPACE: usize = 8 // Account discriminator added by Anchor for each account
+        + 1 // bump
+        + 32 // game
+        + 4 // move_count
+        + 4; // length of the moves vector
+}
+
+impl GameLogAccount {
+    /// Account size once `move_count` moves are stored.
+    pub fn space_for(move_count: usize) -> usize {
+        Self::INIT_SPACE + move_log_len(move_count)
+    }
+
+    pub fn record(&mut self, direction: Direction) {
+        let index = self.move_count as usize;
+        self.moves.resize(move_log_len(index + 1), 0);
+        write_move(&mut self.moves, index, direction.into());
+        self.move_count += 1;
+    }
+}
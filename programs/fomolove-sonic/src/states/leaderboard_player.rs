@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+use crate::{TopGame, MAX_ENTRIES_PER_PLAYER};
+
+/// Games a player holds on one season leaderboard, best first, so a submit
+/// finds the entry it replaces without scanning the board. Entries pushed off
+/// a full board by other players are pruned on the player's next submit.
+#[account]
+pub struct LeaderboardPlayerAccount {
+    pub bump: u8,
+    pub leaderboard: Pubkey,
+    pub player: Pubkey,
+    pub entries: Vec<TopGame>,
+}
+
+impl Space for LeaderboardPlayerAccount {
+    const INIT_SPACE: usize = 8 // Account discriminator added by Anchor for each account
+        + 1 // bump
+        + 32 // leaderboard
+        + 32 // player
+        + 4 + MAX_ENTRIES_PER_PLAYER as usize * TopGame::SPACE; // entries
+}
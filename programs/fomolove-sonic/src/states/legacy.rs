@@ -0,0 +1,383 @@
+//! Layouts of the accounts written before season ids were widened to `u32`,
+//! as the first release of the program wrote them, read once by the migration
+//! instructions.
+
+use anchor_lang::{prelude::*, Discriminator};
+
+use crate::{
+    error::FomoLoveErrorCode, ConfigAccount, GameMode, ModeRules, RandomnessMode, ScoringRules, SeasonAccount,
+    SeasonParams, SpecialTileRules, TeamType, TopGame, WinnerAccount, WinnerTopGame, DEFAULT_MAX_WINNER_COUNT,
+    GAME_MODE_COUNT, MAX_ENTRIES_PER_PLAYER,
+};
+
+/// Games the single leaderboard of a legacy season was sized for.
+pub const LEGACY_LEADERBOARD_MAX_GAME_COUNT: u8 = 10;
+/// Score a game had to beat to enter an empty legacy leaderboard.
+pub const LEGACY_LEADERBOARD_MIN_SCORE: u32 = 0;
+/// Tile a game had to reach to enter an empty legacy leaderboard.
+pub const LEGACY_LEADERBOARD_MIN_TILE: u16 = 2;
+
+/// Decodes a legacy account of type `T`, which has to be owned by the
+/// program, carry the discriminator of `T` and be `space` bytes long.
+pub fn read_legacy_account<T: AnchorDeserialize>(
+    account_info: &AccountInfo,
+    discriminator: [u8; 8],
+    space: usize,
+) -> Result<T> {
+    require_keys_eq!(*account_info.owner, crate::ID, FomoLoveErrorCode::InvalidLegacyAccount);
+    let data = account_info.try_borrow_data()?;
+    require!(
+        data.len() == space && data[..8] == discriminator,
+        FomoLoveErrorCode::InvalidLegacyAccount
+    );
+    Ok(T::deserialize(&mut &data[8..])?)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyConfigAccount {
+    pub bump: u8,
+    pub is_initialized: bool,
+    pub maintainer: Pubkey,
+    pub current_season_id: u8,
+    pub current_season_ended_at: u64,
+    pub season_duration: u64,
+}
+
+impl LegacyConfigAccount {
+    pub const SPACE: usize = 8 // Account discriminator added by Anchor for each account
+        + 1 // bump
+        + 1 // is_initialized
+        + 32 // maintainer
+        + 1 // current_season_id
+        + 8 // current_season_ended_at
+        + 8; // season_duration
+
+    pub fn read(account_info: &AccountInfo) -> Result<Self> {
+        read_legacy_account(account_info, ConfigAccount::DISCRIMINATOR, Self::SPACE)
+    }
+
+    /// Legacy seasons spawned from SlotHashes, no oracle is configured.
+    pub fn migrate(self) -> ConfigAccount {
+        ConfigAccount {
+            bump: self.bump,
+            is_initialized: self.is_initialized,
+            maintainer: self.maintainer,
+            current_season_id: self.current_season_id as u32,
+            current_season_ended_at: self.current_season_ended_at,
+            season_duration: self.season_duration,
+            randomness_mode: RandomnessMode::SlotHashes,
+            randomness_oracle: Pubkey::default(),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyWinnerTopGame {
+    pub user: Pubkey,
+    pub team: TeamType,
+    pub season_id: u8,
+    pub score: u32,
+    pub top_tile: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyWinnerAccount {
+    pub bump: u8,
+    pub max_winner_count: u8,
+    pub leaderboard: Vec<LegacyWinnerTopGame>,
+}
+
+impl LegacyWinnerAccount {
+    pub const SPACE: usize = 8 // Account discriminator added by Anchor for each account
+        + 1 // bump
+        + 1 // max_winner_count
+        + 4 // length of the leaderboard vector
+        + DEFAULT_MAX_WINNER_COUNT as usize * (
+            32 // user
+            + 1 // team
+            + 1 // season_id
+            + 4 // score
+            + 2 // top_tile
+        );
+
+    pub fn read(account_info: &AccountInfo) -> Result<Self> {
+        read_legacy_account(account_info, WinnerAccount::DISCRIMINATOR, Self::SPACE)
+    }
+
+    pub fn migrate(self) -> WinnerAccount {
+        WinnerAccount {
+            bump: self.bump,
+            max_winner_count: self.max_winner_count,
+            leaderboard: self
+                .leaderboard
+                .into_iter()
+                .map(|game| WinnerTopGame {
+                    user: game.user,
+                    team: game.team,
+                    season_id: game.season_id as u32,
+                    score: game.score as u64,
+                    top_tile: game.top_tile as u32,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct LegacyTopGame {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    pub team: TeamType,
+    pub score: u32,
+    pub top_tile: u16,
+}
+
+impl LegacyTopGame {
+    /// Legacy games did not count their moves.
+    pub fn migrate(self) -> TopGame {
+        TopGame {
+            game: self.game,
+            player: self.player,
+            team: self.team,
+            score: self.score as u64,
+            top_tile: self.top_tile as u32,
+            move_count: 0,
+        }
+    }
+}
+
+/// The one leaderboard of a legacy season, ranked by score like a
+/// `GameMode::Classic` board.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LegacyLeaderBoard {
+    pub min_tile: u16,
+    pub max_game_count: u8,
+    pub min_score: u32,
+    pub top_games: Vec<LegacyTopGame>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacySeasonAccount {
+    pub bump: u8,
+    pub season_id: u8,
+    pub started_at: u64,
+    pub ended_at: u64,
+    pub total_game_played: u64,
+    pub leaderboard: LegacyLeaderBoard,
+}
+
+impl LegacySeasonAccount {
+    pub const SPACE: usize = 8 // Account discriminator added by Anchor for each account
+        + 1 // bump
+        + 1 // season_id
+        + 8 // started_at
+        + 8 // ended_at
+        + 8 // total_game_played
+        + 2 // min_tile
+        + 1 // max_game_count
+        + 4 // min_score
+        + 4 + LEGACY_LEADERBOARD_MAX_GAME_COUNT as usize * (
+            32 // game
+            + 32 // player
+            + 1 // team
+            + 4 // score
+            + 2 // top_tile
+        ); // top_games
+
+    pub fn read(account_info: &AccountInfo) -> Result<Self> {
+        read_legacy_account(account_info, SeasonAccount::DISCRIMINATOR, Self::SPACE)
+    }
+
+    /// Rules the legacy season was played with: vanilla classic games on a
+    /// 4x4 board, any number of entries per player and no entry fee. The
+    /// leaderboard raises its admission again once its games are restored.
+    pub fn params(&self) -> SeasonParams {
+        let leaderboard = &self.leaderboard;
+        SeasonParams {
+            duration: self.ended_at.saturating_sub(self.started_at),
+            leaderboard_size: leaderboard.max_game_count as u16,
+            entries_per_player: leaderboard.max_game_count.min(MAX_ENTRIES_PER_PLAYER),
+            min_score: LEGACY_LEADERBOARD_MIN_SCORE as u64,
+            min_tile: LEGACY_LEADERBOARD_MIN_TILE as u32,
+            entry_fee: 0,
+            board_size: 4,
+            mode_rules: ModeRules {
+                enabled_modes: 1 << GameMode::Classic.index(),
+                time_limit: 0,
+                move_limit: 0,
+                sprint_target_tile: 0,
+            },
+            special_tiles: SpecialTileRules::default(),
+            scoring: ScoringRules::default(),
+        }
+    }
+
+    /// Games of the legacy leaderboard in classic rank order.
+    pub fn ranked_games(&self) -> Vec<TopGame> {
+        let mut games: Vec<TopGame> = self.leaderboard.top_games.iter().map(|game| game.migrate()).collect();
+        games.sort_by(|a, b| GameMode::Classic.rank(a, b));
+        games
+    }
+
+    /// The season under its `u32` id, `bump` being the bump of its new address
+    /// and `leaderboard` the classic board its games are copied to.
+    pub fn migrate(self, bump: u8, leaderboard: Pubkey) -> SeasonAccount {
+        let mut leaderboards = [Pubkey::default(); GAME_MODE_COUNT];
+        leaderboards[GameMode::Classic.index()] = leaderboard;
+
+        SeasonAccount {
+            bump,
+            season_id: self.season_id as u32,
+            started_at: self.started_at,
+            ended_at: self.ended_at,
+            total_game_played: self.total_game_played,
+            randomness_mode: RandomnessMode::SlotHashes,
+            params: self.params(),
+            entry_fees: 0,
+            finalized: false,
+            paused_at: 0,
+            paused_time: 0,
+            leaderboards,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Account data as the first release serialized it, padded to `space`.
+    fn legacy_data<T: AnchorSerialize>(discriminator: [u8; 8], account: &T, space: usize) -> Vec<u8> {
+        let mut data = discriminator.to_vec();
+        account.serialize(&mut data).unwrap();
+        assert!(data.len() <= space);
+        data.resize(space, 0);
+        data
+    }
+
+    fn read<T: AnchorDeserialize>(mut data: Vec<u8>, discriminator: [u8; 8], space: usize) -> Result<T> {
+        let key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let owner = crate::ID;
+        let account_info = AccountInfo::new(&key, false, true, &mut lamports, &mut data, &owner, false, 0);
+        read_legacy_account(&account_info, discriminator, space)
+    }
+
+    fn legacy_game(score: u32, top_tile: u16) -> LegacyTopGame {
+        LegacyTopGame {
+            game: Pubkey::new_unique(),
+            player: Pubkey::new_unique(),
+            team: TeamType::ChainTeam,
+            score,
+            top_tile,
+        }
+    }
+
+    #[test]
+    fn legacy_spaces_match_the_first_release() {
+        assert_eq!(LegacyConfigAccount::SPACE, 59);
+        assert_eq!(LegacyWinnerAccount::SPACE, 414);
+        assert_eq!(LegacySeasonAccount::SPACE, 755);
+    }
+
+    #[test]
+    fn migrates_a_legacy_config() {
+        let maintainer = Pubkey::new_unique();
+        let legacy = LegacyConfigAccount {
+            bump: 254,
+            is_initialized: true,
+            maintainer,
+            current_season_id: 7,
+            current_season_ended_at: 1_700_000_000,
+            season_duration: 86_400,
+        };
+        let data = legacy_data(ConfigAccount::DISCRIMINATOR, &legacy, LegacyConfigAccount::SPACE);
+
+        let config = read::<LegacyConfigAccount>(data, ConfigAccount::DISCRIMINATOR, LegacyConfigAccount::SPACE)
+            .unwrap()
+            .migrate();
+        assert_eq!((config.bump, config.maintainer, config.current_season_id), (254, maintainer, 7));
+        assert_eq!((config.current_season_ended_at, config.season_duration), (1_700_000_000, 86_400));
+        assert_eq!(config.randomness_mode, RandomnessMode::SlotHashes);
+    }
+
+    #[test]
+    fn migrates_a_legacy_winner() {
+        let user = Pubkey::new_unique();
+        let legacy = LegacyWinnerAccount {
+            bump: 253,
+            max_winner_count: DEFAULT_MAX_WINNER_COUNT,
+            leaderboard: vec![LegacyWinnerTopGame {
+                user,
+                team: TeamType::MemeTeam,
+                season_id: 3,
+                score: 20_000,
+                top_tile: 2048,
+            }],
+        };
+        let data = legacy_data(WinnerAccount::DISCRIMINATOR, &legacy, LegacyWinnerAccount::SPACE);
+
+        let winner = read::<LegacyWinnerAccount>(data, WinnerAccount::DISCRIMINATOR, LegacyWinnerAccount::SPACE)
+            .unwrap()
+            .migrate();
+        assert_eq!(winner.leaderboard.len(), 1);
+        let game = winner.leaderboard[0];
+        assert_eq!((game.user, game.team, game.season_id), (user, TeamType::MemeTeam, 3));
+        assert_eq!((game.score, game.top_tile), (20_000, 2048));
+    }
+
+    #[test]
+    fn migrates_a_full_legacy_season() {
+        let top_games: Vec<LegacyTopGame> = (0..LEGACY_LEADERBOARD_MAX_GAME_COUNT as u32)
+            .map(|rank| legacy_game(10_000 - rank * 100, 1024))
+            .collect();
+        let legacy = LegacySeasonAccount {
+            bump: 252,
+            season_id: 4,
+            started_at: 1_000,
+            ended_at: 87_400,
+            total_game_played: 42,
+            leaderboard: LegacyLeaderBoard {
+                min_tile: 1024,
+                max_game_count: LEGACY_LEADERBOARD_MAX_GAME_COUNT,
+                min_score: 9_100,
+                top_games: top_games.clone(),
+            },
+        };
+        let data = legacy_data(SeasonAccount::DISCRIMINATOR, &legacy, LegacySeasonAccount::SPACE);
+
+        let legacy = read::<LegacySeasonAccount>(data, SeasonAccount::DISCRIMINATOR, LegacySeasonAccount::SPACE).unwrap();
+        let games = legacy.ranked_games();
+        let leaderboard = Pubkey::new_unique();
+        let season = legacy.migrate(251, leaderboard);
+
+        assert_eq!((season.bump, season.season_id, season.total_game_played), (251, 4, 42));
+        assert_eq!((season.params.duration, season.params.leaderboard_size), (86_400, 10));
+        assert_eq!((season.params.min_score, season.params.min_tile), (0, 2));
+        assert!(season.params.mode_rules.is_enabled(GameMode::Classic));
+        assert!(!season.params.mode_rules.is_enabled(GameMode::Sprint));
+        season.params.validate().unwrap();
+        assert_eq!(season.leaderboards[GameMode::Classic.index()], leaderboard);
+
+        assert_eq!(games.len(), top_games.len());
+        for (game, legacy_game) in games.iter().zip(&top_games) {
+            assert_eq!((game.game, game.player), (legacy_game.game, legacy_game.player));
+            assert_eq!((game.score, game.top_tile), (legacy_game.score as u64, legacy_game.top_tile as u32));
+        }
+    }
+
+    #[test]
+    fn rejects_accounts_of_another_size() {
+        let legacy = LegacyConfigAccount {
+            bump: 1,
+            is_initialized: true,
+            maintainer: Pubkey::new_unique(),
+            current_season_id: 1,
+            current_season_ended_at: 0,
+            season_duration: 0,
+        };
+        let data = legacy_data(ConfigAccount::DISCRIMINATOR, &legacy, ConfigAccount::INIT_SPACE);
+
+        assert!(read::<LegacyConfigAccount>(data, ConfigAccount::DISCRIMINATOR, LegacyConfigAccount::SPACE).is_err());
+    }
+}
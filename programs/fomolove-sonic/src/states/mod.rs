@@ -4,12 +4,42 @@ pub use config::*;
 pub mod season;
 pub use season::*;
 
+pub mod season_params;
+pub use season_params::*;
+
+pub mod season_leaderboard;
+pub use season_leaderboard::*;
+
+pub mod leaderboard_player;
+pub use leaderboard_player::*;
+
+pub mod season_result;
+pub use season_result::*;
+
+pub mod season_schedule;
+pub use season_schedule::*;
+
 pub mod team;
 pub use team::*;
 
+pub mod team_season;
+pub use team_season::*;
+
 pub mod game;
 pub use game::*;
 
+pub mod mode;
+pub use mode::*;
+
+pub mod special_tiles;
+pub use special_tiles::*;
+
+pub mod scoring;
+pub use scoring::*;
+
+pub mod game_log;
+pub use game_log::*;
+
 pub mod user;
 pub use user::*;
 
@@ -17,4 +47,13 @@ pub mod user_season;
 pub use user_season::*;
 
 pub mod winner;
-pub use winner::*;
\ No newline at end of file
+pub use winner::*;
+
+pub mod randomness;
+pub use randomness::*;
+
+pub mod daily_challenge;
+pub use daily_challenge::*;
+
+pub mod legacy;
+pub use legacy::*;
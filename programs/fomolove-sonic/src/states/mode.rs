@@ -0,0 +1,97 @@
+use std::cmp::Ordering;
+
+use anchor_lang::prelude::*;
+
+use crate::{error::FomoLoveErrorCode, TopGame};
+
+pub const GAME_MODE_COUNT: usize = 4;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GameMode {
+    /// Plays until no move is left, ranked by score.
+    Classic,
+    /// Ends `time_limit` seconds after registration, ranked by score.
+    TimeAttack,
+    /// Ends after `move_limit` moves, ranked by score.
+    MoveLimited,
+    /// Ends on reaching `sprint_target_tile`, ranked by the fewest moves.
+    Sprint,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; GAME_MODE_COUNT] = [
+        GameMode::Classic,
+        GameMode::TimeAttack,
+        GameMode::MoveLimited,
+        GameMode::Sprint,
+    ];
+
+    /// Index of the mode leaderboard on `SeasonAccount`.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    fn flag(self) -> u8 {
+        1 << self.index()
+    }
+
+    /// Order of the mode leaderboard, best game first. Sprint games rank by
+    /// the fewest moves, every other mode by score then top tile.
+    pub fn rank(self, a: &TopGame, b: &TopGame) -> Ordering {
+        match self {
+            GameMode::Sprint => a
+                .move_count
+                .cmp(&b.move_count)
+                .then_with(|| b.score.cmp(&a.score)),
+            GameMode::Classic | GameMode::TimeAttack | GameMode::MoveLimited => b
+                .score
+                .cmp(&a.score)
+                .then_with(|| b.top_tile.cmp(&a.top_tile)),
+        }
+    }
+}
+
+/// Modes a season allows and their limits.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ModeRules {
+    /// Bit `GameMode::index` is set for every mode games can register with
+    pub enabled_modes: u8,
+    /// Seconds a TimeAttack game lasts
+    pub time_limit: u64,
+    /// Moves a MoveLimited game lasts
+    pub move_limit: u32,
+    /// Real value of the tile a Sprint game races to
+    pub sprint_target_tile: u32,
+}
+
+impl ModeRules {
+    pub const SPACE: usize = 1 // enabled_modes
+        + 8 // time_limit
+        + 4 // move_limit
+        + 4; // sprint_target_tile
+
+    pub fn is_enabled(&self, mode: GameMode) -> bool {
+        self.enabled_modes & mode.flag() != 0
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.enabled_modes != 0 && self.enabled_modes >> GAME_MODE_COUNT == 0,
+            FomoLoveErrorCode::InvalidModeRules
+        );
+        if self.is_enabled(GameMode::TimeAttack) {
+            require!(self.time_limit > 0, FomoLoveErrorCode::InvalidModeRules);
+        }
+        if self.is_enabled(GameMode::MoveLimited) {
+            require!(self.move_limit > 0, FomoLoveErrorCode::InvalidModeRules);
+        }
+        if self.is_enabled(GameMode::Sprint) {
+            // Spawned tiles are 2 or 4, the target has to be reached by a merge
+            require!(
+                self.sprint_target_tile > 4 && self.sprint_target_tile.is_power_of_two(),
+                FomoLoveErrorCode::InvalidModeRules
+            );
+        }
+        Ok(())
+    }
+}
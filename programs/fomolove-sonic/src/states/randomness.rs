@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+use crate::{
+    error::FomoLoveErrorCode,
+    recent_slot_hash,
+    vrf::{ecvrf_verify, VRF_PROOF_LEN},
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RandomnessMode {
+    /// The tile is spawned in `make_move` from the SlotHashes sysvar.
+    SlotHashes,
+    /// `make_move` leaves a pending spawn that is revealed with `fulfill_spawn`
+    /// from a VRF proof of the configured oracle.
+    Oracle,
+    /// Daily challenge games only: every spawn is derived from the challenge
+    /// seed and the move counter.
+    Challenge,
+}
+
+/// Supplies the 32 bytes of randomness a spawn is derived from.
+pub trait RandomnessProvider {
+    fn randomness(&self) -> Result<[u8; 32]>;
+}
+
+/// Latest slot hash mixed with the player that submitted the move.
+pub struct SlotHashesProvider<'a, 'info> {
+    pub slot_hashes: &'a AccountInfo<'info>,
+    pub player: Pubkey,
+}
+
+impl RandomnessProvider for SlotHashesProvider<'_, '_> {
+    fn randomness(&self) -> Result<[u8; 32]> {
+        let slot_hash = recent_slot_hash(self.slot_hashes)?;
+        Ok(keccak::hashv(&[&slot_hash, self.player.as_ref()]).0)
+    }
+}
+
+/// VRF output of the oracle stored in `ConfigAccount` over the pending spawn.
+///
+/// The proof binds the output to the oracle key and to `alpha`, so the oracle
+/// cannot choose it and anyone holding the proof can reveal the spawn.
+pub struct VrfProvider<'a> {
+    pub oracle: &'a Pubkey,
+    pub alpha: &'a [u8],
+    pub proof: &'a [u8; VRF_PROOF_LEN],
+}
+
+impl VrfProvider<'_> {
+    /// The oracle proves over the game and the move that left the spawn pending.
+    pub fn alpha(game: &Pubkey, move_count: u32) -> [u8; 36] {
+        let mut alpha = [0u8; 36];
+        alpha[..32].copy_from_slice(game.as_ref());
+        alpha[32..].copy_from_slice(&move_count.to_le_bytes());
+        alpha
+    }
+}
+
+impl RandomnessProvider for VrfProvider<'_> {
+    fn randomness(&self) -> Result<[u8; 32]> {
+        let output = ecvrf_verify(&self.oracle.to_bytes(), self.alpha, self.proof)
+            .ok_or(FomoLoveErrorCode::InvalidVrfProof)?;
+
+        let mut randomness = [0u8; 32];
+        randomness.copy_from_slice(&output[..32]);
+        Ok(randomness)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vrf::prove;
+
+    /// Trusts whatever the configured key reveals, for tests that don't need a proof.
+    struct SignedOracleProvider {
+        oracle: Pubkey,
+        authority: Pubkey,
+        revealed: [u8; 32],
+    }
+
+    impl RandomnessProvider for SignedOracleProvider {
+        fn randomness(&self) -> Result<[u8; 32]> {
+            require_keys_eq!(self.oracle, self.authority, FomoLoveErrorCode::Unauthorized);
+            Ok(self.revealed)
+        }
+    }
+
+    #[test]
+    fn mock_oracle_only_trusts_its_key() {
+        let oracle = Pubkey::new_unique();
+        let mock = |authority| SignedOracleProvider { oracle, authority, revealed: [3; 32] };
+
+        assert_eq!(mock(oracle).randomness().unwrap(), [3; 32]);
+        assert!(mock(Pubkey::new_unique()).randomness().is_err());
+    }
+
+    #[test]
+    fn vrf_provider_reveals_the_proven_output() {
+        let game = Pubkey::new_unique();
+        let alpha = VrfProvider::alpha(&game, 5);
+        let (oracle, proof) = prove(&[9; 32], &alpha);
+        let oracle = Pubkey::new_from_array(oracle);
+
+        let provider = VrfProvider { oracle: &oracle, alpha: &alpha, proof: &proof };
+        let expected = ecvrf_verify(&oracle.to_bytes(), &alpha, &proof).unwrap();
+        assert_eq!(provider.randomness().unwrap()[..], expected[..32]);
+
+        let next_move = VrfProvider::alpha(&game, 6);
+        let provider = VrfProvider { oracle: &oracle, alpha: &next_move, proof: &proof };
+        assert!(provider.randomness().is_err());
+    }
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::error::FomoLoveErrorCode;
+
+/// Score bonuses in percent of the merge score, see `fomolove_engine::scoring`.
+/// All zero scores the merged tile values only.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct ScoringRules {
+    /// Added for every merge of a move beyond the first
+    pub combo_bonus: u16,
+    /// Added for every merging move right before this one
+    pub streak_bonus: u16,
+    /// Longest streak the bonus counts, 0 for no limit
+    pub streak_cap: u8,
+}
+
+impl ScoringRules {
+    pub const SPACE: usize = 2 // combo_bonus
+        + 2 // streak_bonus
+        + 1; // streak_cap
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            fomolove_engine::ScoreBonuses::from(*self).is_valid(),
+            FomoLoveErrorCode::InvalidScoringRules
+        );
+        Ok(())
+    }
+}
+
+impl From<ScoringRules> for fomolove_engine::ScoreBonuses {
+    fn from(rules: ScoringRules) -> Self {
+        fomolove_engine::ScoreBonuses {
+            combo_bonus: rules.combo_bonus,
+            streak_bonus: rules.streak_bonus,
+            streak_cap: rules.streak_cap,
+        }
+    }
+}
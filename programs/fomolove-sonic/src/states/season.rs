@@ -1,50 +1,196 @@
+use std::cmp::Ordering;
+
 use anchor_lang::prelude::*;
 
-use crate::{TeamType, DEFAULT_LEADERBOARD_MAX_GAME_COUNT};
+use crate::{error::FomoLoveErrorCode, GameMode, RandomnessMode, SeasonParams, TeamType, GAME_MODE_COUNT};
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
 pub struct TopGame {
     pub game: Pubkey,
     pub player: Pubkey,
     pub team: TeamType,
-    pub score: u32,
-    pub top_tile: u16, // Updated to u16
+    pub score: u64,
+    pub top_tile: u32,
+    pub move_count: u32,
+}
+
+impl TopGame {
+    pub const SPACE: usize = 32 // game
+        + 32 // player
+        + 1 // team
+        + 8 // score
+        + 4 // top_tile
+        + 4; // move_count
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
 pub struct LeaderBoard {
-    pub min_tile: u16, // Updated to u16
+    pub mode: GameMode,
+    pub min_tile: u32,
     pub max_game_count: u8,
-    pub min_score: u32,
+    pub min_score: u64,
+    /// Entries a single player can hold
+    pub entries_per_player: u8,
     pub top_games: Vec<TopGame>,
 }
 
+impl LeaderBoard {
+    /// Without its games, `TopGame::SPACE` each
+    pub const SPACE: usize = 1 // mode
+        + 4 // min_tile
+        + 1 // max_game_count
+        + 8 // min_score
+        + 1 // entries_per_player
+        + 4; // length of the top_games vector
+
+    /// Empty leaderboard of `max_game_count` games gated as `params` says.
+    pub fn new(mode: GameMode, max_game_count: u8, params: &SeasonParams) -> Self {
+        LeaderBoard {
+            mode,
+            min_score: params.min_score,
+            min_tile: params.min_tile,
+            entries_per_player: params.entries_per_player,
+            top_games: Vec::new(),
+            max_game_count,
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.top_games.len() >= self.max_game_count as usize
+    }
+
+    /// Ranks a game on the board, `target_tile` being its Sprint goal. Fails
+    /// when the game does not earn a place. A player holding all their
+    /// entries replaces their worst one.
+    pub fn submit(&mut self, top_game: TopGame, target_tile: u32) -> Result<()> {
+        let bottom_game = if self.is_full() { self.top_games.last() } else { None };
+        check_admission(self.mode, &top_game, target_tile, self.min_score, self.min_tile, bottom_game)?;
+
+        let mode = self.mode;
+        let max_game_count = self.max_game_count as usize;
+        let replaced = replaced_entry(mode, self.entries_per_player, &top_game, self.top_games.iter().copied())?;
+        let top_games = &mut self.top_games;
+
+        if let Some(replaced) = replaced {
+            top_games.remove(replaced);
+        }
+
+        // Ties keep the earlier submission first
+        let position = top_games.partition_point(|game| mode.rank(game, &top_game) != Ordering::Greater);
+        top_games.insert(position, top_game);
+
+        // Limit quantity of games in leaderboard
+        top_games.truncate(max_game_count);
+
+        // Update min_score and min_tile if the leaderboard is full
+        if top_games.len() == max_game_count {
+            if let Some(bottom_game) = top_games.last() {
+                self.min_score = bottom_game.score;
+                self.min_tile = bottom_game.top_tile;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Fails when `top_game` does not earn a place on a `mode` leaderboard,
+/// `bottom_game` being its last game once it is full.
+pub fn check_admission(
+    mode: GameMode,
+    top_game: &TopGame,
+    target_tile: u32,
+    min_score: u64,
+    min_tile: u32,
+    bottom_game: Option<&TopGame>,
+) -> Result<()> {
+    match mode {
+        GameMode::Sprint => {
+            require!(
+                top_game.top_tile >= target_tile,
+                FomoLoveErrorCode::SprintTargetNotReached
+            );
+            if let Some(bottom_game) = bottom_game {
+                require!(
+                    GameMode::Sprint.rank(top_game, bottom_game) == Ordering::Less,
+                    FomoLoveErrorCode::TooManySprintMoves
+                );
+            }
+        }
+        GameMode::Classic | GameMode::TimeAttack | GameMode::MoveLimited => {
+            require!(top_game.top_tile >= min_tile, FomoLoveErrorCode::LowTile);
+            require!(top_game.score > min_score, FomoLoveErrorCode::LowScore);
+        }
+    }
+    Ok(())
+}
+
+/// Index in the ranked `top_games` of the entry `top_game` replaces when
+/// its player already holds `entries_per_player` of them, their worst one.
+/// Fails when the game does not rank above it.
+pub fn replaced_entry(
+    mode: GameMode,
+    entries_per_player: u8,
+    top_game: &TopGame,
+    top_games: impl Iterator<Item = TopGame>,
+) -> Result<Option<usize>> {
+    let mut entries = 0;
+    let mut worst = None;
+    for (index, game) in top_games.enumerate() {
+        if game.player == top_game.player {
+            entries += 1;
+            worst = Some((index, game));
+        }
+    }
+
+    match worst {
+        Some((index, worst_game)) if entries >= entries_per_player as usize => {
+            require!(
+                mode.rank(top_game, &worst_game) == Ordering::Less,
+                FomoLoveErrorCode::PlayerEntryNotImproved
+            );
+            Ok(Some(index))
+        }
+        _ => Ok(None),
+    }
+}
+
 #[account]
 pub struct SeasonAccount {
     pub bump: u8,
-    pub season_id: u8,
+    pub season_id: u32,
     pub started_at: u64,
     pub ended_at: u64,
     pub total_game_played: u64,
-    pub leaderboard: LeaderBoard,
+    pub randomness_mode: RandomnessMode,
+    pub params: SeasonParams,
+    /// Lamports collected from entry fees, held by this account until
+    /// `withdraw_entry_fees`
+    pub entry_fees: u64,
+    /// Set by `finalize_season`, the leaderboards no longer change
+    pub finalized: bool,
+    /// When `pause_season` stopped play, 0 while the season runs
+    pub paused_at: u64,
+    /// Seconds the season spent paused, TimeAttack deadlines are pushed back
+    /// by it
+    pub paused_time: u64,
+    /// `SeasonLeaderboardAccount` of every `GameMode`, at `GameMode::index`,
+    /// default until created
+    pub leaderboards: [Pubkey; GAME_MODE_COUNT],
 }
 
 impl Space for SeasonAccount {
     const INIT_SPACE: usize = 8 // Account discriminator added by Anchor for each account
         + 1 // bump
-        + 1 // season_id
+        + 4 // season_id
         + 8 // started_at
         + 8 // ended_at
         + 8 // total_game_played
-        + 2 // min_tile (updated to u16, hence 2 bytes)
-        + 1 // max_game_count
-        + 4 // min_score
-        // top_games
-        + 4 + DEFAULT_LEADERBOARD_MAX_GAME_COUNT as usize * (
-            32 // game
-            + 32 // player
-            + 4 // score
-            + 1 //team type
-            + 2 // top_tile (updated to u16, hence 2 bytes)
-        ); // TopGame
+        + 1 // randomness_mode
+        + SeasonParams::SPACE // params
+        + 8 // entry_fees
+        + 1 // finalized
+        + 8 // paused_at
+        + 8 // paused_time
+        + GAME_MODE_COUNT * 32; // leaderboards
 }
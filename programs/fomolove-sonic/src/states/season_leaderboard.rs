@@ -0,0 +1,492 @@
+// The padding check of bytemuck's `Pod` derive builds a struct whose field
+// is never read, for every zero-copy type below
+#![allow(dead_code)]
+
+use std::cell::{Ref, RefMut};
+use std::cmp::Ordering;
+use std::mem::size_of;
+
+use anchor_lang::prelude::*;
+
+use crate::{check_admission, error::FomoLoveErrorCode, GameMode, SeasonParams, TeamType, TopGame};
+
+/// Header of the leaderboard of one mode of a season. `max_game_count`
+/// `LeaderboardEntry` follow it in the account data, best game first, so the
+/// board is not bound by the Borsh (de)serialization of every submit.
+///
+/// The account is too large for a CPI create: the client creates it with
+/// `SeasonLeaderboardAccount::space_for` bytes, then `create_season_leaderboard`
+/// initializes it.
+#[account(zero_copy)]
+pub struct SeasonLeaderboardAccount {
+    pub season: Pubkey,
+    /// Score a game has to beat to enter, the last game's once full
+    pub min_score: u64,
+    /// Real value of the tile a game has to reach to enter
+    pub min_tile: u32,
+    pub max_game_count: u16,
+    pub game_count: u16,
+    /// `GameMode::index` of the mode ranked
+    pub mode: u8,
+    /// Entries a single player can hold
+    pub entries_per_player: u8,
+    pub padding: [u8; 6],
+}
+
+/// `TopGame` laid out for zero-copy.
+#[zero_copy]
+pub struct LeaderboardEntry {
+    pub game: Pubkey,
+    pub player: Pubkey,
+    pub score: u64,
+    pub top_tile: u32,
+    pub move_count: u32,
+    /// `TeamType` as `u8`
+    pub team: u8,
+    pub padding: [u8; 7],
+}
+
+impl From<TopGame> for LeaderboardEntry {
+    fn from(top_game: TopGame) -> Self {
+        LeaderboardEntry {
+            game: top_game.game,
+            player: top_game.player,
+            score: top_game.score,
+            top_tile: top_game.top_tile,
+            move_count: top_game.move_count,
+            team: top_game.team as u8,
+            padding: [0; 7],
+        }
+    }
+}
+
+impl LeaderboardEntry {
+    pub fn top_game(&self) -> TopGame {
+        let team = match self.team {
+            1 => TeamType::MemeTeam,
+            2 => TeamType::ChainTeam,
+            _ => TeamType::None,
+        };
+        TopGame {
+            game: self.game,
+            player: self.player,
+            team,
+            score: self.score,
+            top_tile: self.top_tile,
+            move_count: self.move_count,
+        }
+    }
+}
+
+impl SeasonLeaderboardAccount {
+    /// Without its games, `LeaderboardEntry` follow
+    pub const SPACE: usize = 8 // Account discriminator added by Anchor for each account
+        + size_of::<SeasonLeaderboardAccount>();
+
+    /// Account size for `max_game_count` games.
+    pub fn space_for(max_game_count: u16) -> usize {
+        Self::SPACE + max_game_count as usize * size_of::<LeaderboardEntry>()
+    }
+
+    pub fn init(&mut self, season: Pubkey, mode: GameMode, params: &SeasonParams) {
+        self.season = season;
+        self.min_score = params.min_score;
+        self.min_tile = params.min_tile;
+        self.max_game_count = params.leaderboard_size;
+        self.game_count = 0;
+        self.mode = mode.index() as u8;
+        self.entries_per_player = params.entries_per_player;
+    }
+
+    pub fn mode(&self) -> GameMode {
+        GameMode::ALL[self.mode as usize]
+    }
+}
+
+/// Ranked games of an initialized leaderboard account, best first, borrowed
+/// from its account data.
+pub fn leaderboard_games<'a>(loader: &'a AccountLoader<'_, SeasonLeaderboardAccount>) -> Result<Ref<'a, [LeaderboardEntry]>> {
+    let game_count = loader.load()?.game_count as usize;
+    let data = loader.as_ref().try_borrow_data()?;
+    check_leaderboard_len(&data)?;
+    Ok(Ref::map(data, |data| {
+        let entries = &data[SeasonLeaderboardAccount::SPACE..];
+        &bytemuck::cast_slice::<u8, LeaderboardEntry>(entries)[..game_count]
+    }))
+}
+
+/// Fails unless `data` holds a header and exactly the `max_game_count` entries
+/// it announces, which the zero-copy casts rely on.
+fn check_leaderboard_len(data: &[u8]) -> Result<()> {
+    require!(
+        data.len() >= SeasonLeaderboardAccount::SPACE,
+        FomoLoveErrorCode::InvalidLeaderboardSize
+    );
+    let header = bytemuck::from_bytes::<SeasonLeaderboardAccount>(&data[8..SeasonLeaderboardAccount::SPACE]);
+    require!(
+        data.len() - SeasonLeaderboardAccount::SPACE == header.max_game_count as usize * size_of::<LeaderboardEntry>(),
+        FomoLoveErrorCode::InvalidLeaderboardSize
+    );
+    require!(
+        header.game_count <= header.max_game_count,
+        FomoLoveErrorCode::InvalidLeaderboardSize
+    );
+    Ok(())
+}
+
+/// Header and games of a season leaderboard, borrowed from its account data.
+pub struct SeasonLeaderboard<'a> {
+    pub header: RefMut<'a, SeasonLeaderboardAccount>,
+    entries: RefMut<'a, [LeaderboardEntry]>,
+}
+
+impl<'a> SeasonLeaderboard<'a> {
+    /// Borrows an initialized leaderboard account. Fails unless the account is
+    /// `space_for(max_game_count)` bytes, as `create_season_leaderboard` checks.
+    pub fn load_mut<'info>(loader: &'a AccountLoader<'info, SeasonLeaderboardAccount>) -> Result<Self> {
+        let data = loader.as_ref().try_borrow_mut_data()?;
+        check_leaderboard_len(&data)?;
+        let (header, entries) = RefMut::map_split(data, |data| {
+            let (header, entries) = data[8..].split_at_mut(size_of::<SeasonLeaderboardAccount>());
+            (
+                bytemuck::from_bytes_mut::<SeasonLeaderboardAccount>(header),
+                bytemuck::cast_slice_mut::<u8, LeaderboardEntry>(entries),
+            )
+        });
+        Ok(SeasonLeaderboard { header, entries })
+    }
+
+    /// Ranked games, best first.
+    pub fn games(&self) -> &[LeaderboardEntry] {
+        &self.entries[..self.header.game_count as usize]
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.header.game_count >= self.header.max_game_count
+    }
+
+    /// Index of `top_game` on the board, found by binary search over the
+    /// games ranked equal to it.
+    pub fn position_of(&self, top_game: &TopGame) -> Option<usize> {
+        let mode = self.header.mode();
+        let games = self.games();
+        let start = games.partition_point(|entry| mode.rank(&entry.top_game(), top_game) == Ordering::Less);
+        let end = games.partition_point(|entry| mode.rank(&entry.top_game(), top_game) != Ordering::Greater);
+        games[start..end]
+            .iter()
+            .position(|entry| entry.game == top_game.game)
+            .map(|offset| start + offset)
+    }
+
+    /// Ranks a game on the board, `target_tile` being its Sprint goal. Fails
+    /// when the game does not earn a place. The slot is found by binary
+    /// search, a player holding all their `player_entries` replaces their
+    /// worst one and a full board otherwise drops its last game. The games
+    /// below the slot still shift down one entry each, so a submit is O(n)
+    /// in the board size.
+    pub fn submit(&mut self, top_game: TopGame, target_tile: u32, player_entries: &mut Vec<TopGame>) -> Result<()> {
+        let mode = self.header.mode();
+        let bottom_game = match self.games().last() {
+            Some(entry) if self.is_full() => Some(entry.top_game()),
+            _ => None,
+        };
+        check_admission(
+            mode,
+            &top_game,
+            target_tile,
+            self.header.min_score,
+            self.header.min_tile,
+            bottom_game.as_ref(),
+        )?;
+
+        let game_count = self.header.game_count as usize;
+        let max_game_count = self.header.max_game_count as usize;
+
+        player_entries.retain(|entry| self.position_of(entry).is_some());
+        let replaced = if player_entries.len() >= self.header.entries_per_player as usize {
+            let worst = *player_entries.last().ok_or(FomoLoveErrorCode::InvalidEntriesPerPlayer)?;
+            require!(
+                mode.rank(&top_game, &worst) == Ordering::Less,
+                FomoLoveErrorCode::PlayerEntryNotImproved
+            );
+            player_entries.pop();
+            self.position_of(&worst)
+        } else {
+            None
+        };
+
+        // Ties keep the earlier submission first
+        let position = self.entries[..game_count]
+            .partition_point(|entry| mode.rank(&entry.top_game(), &top_game) != Ordering::Greater);
+        require!(position < max_game_count, FomoLoveErrorCode::LowScore);
+
+        // Entries from `position` move down one slot over the replaced entry,
+        // or over the free slot past the last game, or off a full board
+        let last = match replaced {
+            Some(replaced) => replaced,
+            None => game_count.min(max_game_count - 1),
+        };
+        self.entries.copy_within(position..last, position + 1);
+        self.entries[position] = top_game.into();
+        if replaced.is_none() {
+            self.header.game_count = (last + 1) as u16;
+        }
+
+        let player_position = player_entries.partition_point(|entry| mode.rank(entry, &top_game) != Ordering::Greater);
+        player_entries.insert(player_position, top_game);
+
+        self.raise_admission();
+        Ok(())
+    }
+
+    /// Fills an empty board with `games`, ranked best first, as a legacy
+    /// season leaderboard held them.
+    pub fn restore(&mut self, games: &[TopGame]) -> Result<()> {
+        require!(
+            self.header.game_count == 0 && games.len() <= self.header.max_game_count as usize,
+            FomoLoveErrorCode::InvalidLeaderboardSize
+        );
+        for (entry, game) in self.entries.iter_mut().zip(games) {
+            *entry = (*game).into();
+        }
+        self.header.game_count = games.len() as u16;
+
+        self.raise_admission();
+        Ok(())
+    }
+
+    /// Update min_score and min_tile if the leaderboard is full
+    fn raise_admission(&mut self) {
+        if self.is_full() {
+            let bottom_game = self.entries[self.header.max_game_count as usize - 1];
+            self.header.min_score = bottom_game.score;
+            self.header.min_tile = bottom_game.top_tile;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    type Board = (RefCell<SeasonLeaderboardAccount>, RefCell<Vec<LeaderboardEntry>>);
+
+    fn leaderboard(max_game_count: u16, min_score: u64) -> Board {
+        let mut header: SeasonLeaderboardAccount = bytemuck::Zeroable::zeroed();
+        header.mode = GameMode::Classic.index() as u8;
+        header.max_game_count = max_game_count;
+        header.min_score = min_score;
+        header.min_tile = 2;
+        header.entries_per_player = 1;
+        let entries = vec![bytemuck::Zeroable::zeroed(); max_game_count as usize];
+        (RefCell::new(header), RefCell::new(entries))
+    }
+
+    fn submit_as(board: &Board, top_game: TopGame, player_entries: &mut Vec<TopGame>) -> Result<()> {
+        let mut leaderboard = SeasonLeaderboard {
+            header: board.0.borrow_mut(),
+            entries: RefMut::map(board.1.borrow_mut(), |entries| entries.as_mut_slice()),
+        };
+        leaderboard.submit(top_game, 0, player_entries)
+    }
+
+    fn restore(board: &Board, games: &[TopGame]) -> Result<()> {
+        let mut leaderboard = SeasonLeaderboard {
+            header: board.0.borrow_mut(),
+            entries: RefMut::map(board.1.borrow_mut(), |entries| entries.as_mut_slice()),
+        };
+        leaderboard.restore(games)
+    }
+
+    fn submit(board: &Board, top_game: TopGame) -> Result<()> {
+        submit_as(board, top_game, &mut Vec::new())
+    }
+
+    fn games(board: &Board) -> Vec<Pubkey> {
+        let game_count = board.0.borrow().game_count as usize;
+        board.1.borrow()[..game_count].iter().map(|entry| entry.game).collect()
+    }
+
+    fn top_game(score: u64) -> TopGame {
+        player_game(Pubkey::new_unique(), score)
+    }
+
+    fn player_game(player: Pubkey, score: u64) -> TopGame {
+        TopGame {
+            game: Pubkey::new_unique(),
+            player,
+            team: TeamType::MemeTeam,
+            score,
+            top_tile: 2048,
+            move_count: 100,
+        }
+    }
+
+    #[test]
+    fn ties_keep_the_earlier_submission_first() {
+        let board = leaderboard(4, 0);
+        let (first, second, best) = (top_game(100), top_game(100), top_game(150));
+        for game in [first, second, best] {
+            submit(&board, game).unwrap();
+        }
+
+        assert_eq!(games(&board), vec![best.game, first.game, second.game]);
+    }
+
+    #[test]
+    fn full_board_drops_its_last_entry() {
+        let board = leaderboard(3, 0);
+        let games_by_score = [100, 200, 300].map(top_game);
+        for game in games_by_score {
+            submit(&board, game).unwrap();
+        }
+        assert_eq!(board.0.borrow().min_score, 100);
+
+        let newcomer = top_game(250);
+        submit(&board, newcomer).unwrap();
+
+        assert_eq!(games(&board), vec![games_by_score[2].game, newcomer.game, games_by_score[1].game]);
+        assert_eq!(board.0.borrow().game_count, 3);
+        assert_eq!(board.0.borrow().min_score, 200);
+    }
+
+    #[test]
+    fn rejects_games_below_the_min_score() {
+        let board = leaderboard(2, 50);
+        assert_eq!(submit(&board, top_game(50)).unwrap_err(), FomoLoveErrorCode::LowScore.into());
+
+        submit(&board, top_game(300)).unwrap();
+        submit(&board, top_game(200)).unwrap();
+        assert_eq!(submit(&board, top_game(150)).unwrap_err(), FomoLoveErrorCode::LowScore.into());
+        assert_eq!(board.0.borrow().game_count, 2);
+    }
+
+    #[test]
+    fn better_game_replaces_the_players_entry() {
+        let board = leaderboard(4, 0);
+        let player = Pubkey::new_unique();
+        let mut entries = Vec::new();
+        let other = top_game(150);
+
+        submit_as(&board, player_game(player, 100), &mut entries).unwrap();
+        submit(&board, other).unwrap();
+        let best = player_game(player, 200);
+        submit_as(&board, best, &mut entries).unwrap();
+
+        assert_eq!(games(&board), vec![best.game, other.game]);
+        assert_eq!(entries.iter().map(|entry| entry.game).collect::<Vec<_>>(), vec![best.game]);
+    }
+
+    #[test]
+    fn worse_game_of_the_player_is_rejected() {
+        let board = leaderboard(4, 0);
+        let player = Pubkey::new_unique();
+        let mut entries = Vec::new();
+        let best = player_game(player, 200);
+        submit_as(&board, best, &mut entries).unwrap();
+
+        assert_eq!(
+            submit_as(&board, player_game(player, 100), &mut entries).unwrap_err(),
+            FomoLoveErrorCode::PlayerEntryNotImproved.into()
+        );
+        assert_eq!(games(&board), vec![best.game]);
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[test]
+    fn players_hold_several_entries() {
+        let board = leaderboard(4, 0);
+        board.0.borrow_mut().entries_per_player = 2;
+        let player = Pubkey::new_unique();
+        let mut entries = Vec::new();
+        let player_games = [100, 300, 200].map(|score| player_game(player, score));
+        for game in player_games {
+            submit_as(&board, game, &mut entries).unwrap();
+        }
+        let other = top_game(150);
+        submit(&board, other).unwrap();
+
+        // The third game replaced the player's worst entry only
+        assert_eq!(games(&board), vec![player_games[1].game, player_games[2].game, other.game]);
+        assert_eq!(
+            entries.iter().map(|entry| entry.game).collect::<Vec<_>>(),
+            vec![player_games[1].game, player_games[2].game]
+        );
+        assert_eq!(
+            submit_as(&board, player_game(player, 190), &mut entries).unwrap_err(),
+            FomoLoveErrorCode::PlayerEntryNotImproved.into()
+        );
+    }
+
+    #[test]
+    fn entries_pushed_off_the_board_are_pruned() {
+        let board = leaderboard(2, 0);
+        board.0.borrow_mut().entries_per_player = 2;
+        let player = Pubkey::new_unique();
+        let mut entries = Vec::new();
+        submit_as(&board, player_game(player, 100), &mut entries).unwrap();
+        let others = [200, 300].map(top_game);
+        for game in others {
+            submit(&board, game).unwrap();
+        }
+        assert_eq!(entries.len(), 1);
+
+        let comeback = player_game(player, 250);
+        submit_as(&board, comeback, &mut entries).unwrap();
+
+        assert_eq!(games(&board), vec![others[1].game, comeback.game]);
+        assert_eq!(entries.iter().map(|entry| entry.game).collect::<Vec<_>>(), vec![comeback.game]);
+    }
+
+    #[test]
+    fn restored_board_ranks_new_games() {
+        let board = leaderboard(3, 0);
+        let restored = [300, 200, 100].map(top_game);
+        restore(&board, &restored).unwrap();
+        assert_eq!(board.0.borrow().min_score, 100);
+        assert_eq!(
+            restore(&board, &restored).unwrap_err(),
+            FomoLoveErrorCode::InvalidLeaderboardSize.into()
+        );
+
+        let newcomer = top_game(250);
+        submit(&board, newcomer).unwrap();
+
+        assert_eq!(games(&board), vec![restored[0].game, newcomer.game, restored[1].game]);
+        assert_eq!(board.0.borrow().min_score, 200);
+    }
+
+    #[test]
+    fn leaderboard_len_must_match_its_header() {
+        let entry_len = size_of::<LeaderboardEntry>();
+        let check = |max_game_count: u16, game_count: u16, len: usize| {
+            // Backed by u64 words, as account data is aligned for the header
+            let mut words = vec![0u64; len.div_ceil(8)];
+            let data = &mut bytemuck::cast_slice_mut::<u64, u8>(&mut words)[..len];
+            if len >= SeasonLeaderboardAccount::SPACE {
+                let header =
+                    bytemuck::from_bytes_mut::<SeasonLeaderboardAccount>(&mut data[8..SeasonLeaderboardAccount::SPACE]);
+                header.max_game_count = max_game_count;
+                header.game_count = game_count;
+            }
+            check_leaderboard_len(data)
+        };
+
+        check(4, 4, SeasonLeaderboardAccount::space_for(4)).unwrap();
+        check(0, 0, SeasonLeaderboardAccount::SPACE).unwrap();
+        for (max_game_count, game_count, len) in [
+            (4, 0, SeasonLeaderboardAccount::space_for(4) - entry_len),
+            (4, 0, SeasonLeaderboardAccount::space_for(4) + 1),
+            (4, 0, SeasonLeaderboardAccount::space_for(5)),
+            (4, 5, SeasonLeaderboardAccount::space_for(4)),
+            (0, 0, SeasonLeaderboardAccount::SPACE - 1),
+        ] {
+            assert_eq!(
+                check(max_game_count, game_count, len).unwrap_err(),
+                FomoLoveErrorCode::InvalidLeaderboardSize.into()
+            );
+        }
+    }
+}
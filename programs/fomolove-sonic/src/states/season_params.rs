@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use fomolove_engine::is_valid_board_size;
+
+use crate::{
+    error::FomoLoveErrorCode, ModeRules, ScoringRules, SpecialTileRules, MAX_DAILY_CHALLENGE_GAME_COUNT,
+    MAX_ENTRIES_PER_PLAYER, MAX_LEADERBOARD_GAME_COUNT,
+};
+
+/// Rules a season is started with, fixed for its whole duration.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct SeasonParams {
+    /// Seconds the season lasts, 0 uses `ConfigAccount::season_duration`
+    pub duration: u64,
+    /// Games kept on each leaderboard of the season, daily challenges keep
+    /// up to `MAX_DAILY_CHALLENGE_GAME_COUNT` of them
+    pub leaderboard_size: u16,
+    /// Entries a single player can hold on a leaderboard
+    pub entries_per_player: u8,
+    /// Score a game has to beat to enter a leaderboard
+    pub min_score: u64,
+    /// Real value of the tile a game has to reach to enter a leaderboard
+    pub min_tile: u32,
+    /// Lamports paid to the season account for every registered game
+    pub entry_fee: u64,
+    pub board_size: u8,
+    pub mode_rules: ModeRules,
+    pub special_tiles: SpecialTileRules,
+    pub scoring: ScoringRules,
+}
+
+impl SeasonParams {
+    pub const SPACE: usize = 8 // duration
+        + 2 // leaderboard_size
+        + 1 // entries_per_player
+        + 8 // min_score
+        + 4 // min_tile
+        + 8 // entry_fee
+        + 1 // board_size
+        + ModeRules::SPACE // mode_rules
+        + SpecialTileRules::SPACE // special_tiles
+        + ScoringRules::SPACE; // scoring
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            self.leaderboard_size > 0 && self.leaderboard_size <= MAX_LEADERBOARD_GAME_COUNT,
+            FomoLoveErrorCode::InvalidLeaderboardSize
+        );
+        require!(
+            self.entries_per_player > 0 && self.entries_per_player <= MAX_ENTRIES_PER_PLAYER,
+            FomoLoveErrorCode::InvalidEntriesPerPlayer
+        );
+        require!(
+            self.min_tile >= 2 && self.min_tile.is_power_of_two(),
+            FomoLoveErrorCode::InvalidMinTile
+        );
+        require!(is_valid_board_size(self.board_size as usize), FomoLoveErrorCode::InvalidBoardSize);
+        self.mode_rules.validate()?;
+        self.special_tiles.validate()?;
+        self.scoring.validate()?;
+        Ok(())
+    }
+
+    /// Seconds the season lasts, `default_duration` when `duration` is 0.
+    pub fn duration_or(&self, default_duration: u64) -> u64 {
+        if self.duration == 0 { default_duration } else { self.duration }
+    }
+
+    /// Games kept on the leaderboard of a daily challenge of the season.
+    pub fn daily_challenge_size(&self) -> u8 {
+        self.leaderboard_size.min(MAX_DAILY_CHALLENGE_GAME_COUNT as u16) as u8
+    }
+}
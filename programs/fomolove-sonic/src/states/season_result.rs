@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::{TeamType, GAME_MODE_COUNT};
+
+/// Frozen leaderboard of one mode. Its account keeps the final ranks, best
+/// game first, and no longer changes once the season is finalized.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct ModeRanking {
+    /// `SeasonLeaderboardAccount` of the mode, default when never created
+    pub leaderboard: Pubkey,
+    pub game_count: u16,
+    /// Player ranked first, default on an empty board
+    pub winner: Pubkey,
+    pub winner_score: u64,
+}
+
+impl ModeRanking {
+    pub const SPACE: usize = 32 // leaderboard
+        + 2 // game_count
+        + 32 // winner
+        + 8; // winner_score
+}
+
+/// Outcome of a season, written once by `finalize_season`. Rewards key off
+/// this account and the frozen leaderboards it points to.
+#[account]
+pub struct SeasonResultAccount {
+    pub bump: u8,
+    pub season_id: u32,
+    pub finalized_at: u64,
+    /// Team with the highest total, `TeamType::None` on a tie
+    pub winning_team: TeamType,
+    /// `TeamSeasonAccount::total_score` of the team
+    pub meme_team_score: u64,
+    pub chain_team_score: u64,
+    /// `TeamSeasonAccount::game_played` of the team
+    pub meme_team_games: u64,
+    pub chain_team_games: u64,
+    /// One ranking per `GameMode`, at `GameMode::index`
+    pub rankings: [ModeRanking; GAME_MODE_COUNT],
+}
+
+impl Space for SeasonResultAccount {
+    const INIT_SPACE: usize = 8 // Account discriminator added by Anchor for each account
+        + 1 // bump
+        + 4 // season_id
+        + 8 // finalized_at
+        + 1 // winning_team
+        + 8 // meme_team_score
+        + 8 // chain_team_score
+        + 8 // meme_team_games
+        + 8 // chain_team_games
+        + GAME_MODE_COUNT * ModeRanking::SPACE; // rankings
+}
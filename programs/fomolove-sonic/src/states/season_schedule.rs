@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{SeasonParams, GAME_MODE_COUNT, MAX_SCHEDULED_SEASONS};
+
+/// A season waiting in the schedule.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct ScheduledSeason {
+    /// Pushed back to the end of the previous season if that ends later,
+    /// or to the crank if the whole season would be over by then
+    pub start_time: u64,
+    pub params: SeasonParams,
+    /// Empty leaderboard accounts of the enabled modes, indexed by
+    /// `GameMode::index`, that the crank initializes for the season. The
+    /// maintainer creates and funds them with `SeasonLeaderboardAccount::space_for`
+    /// bytes before scheduling.
+    pub leaderboards: [Pubkey; GAME_MODE_COUNT],
+}
+
+impl ScheduledSeason {
+    pub const SPACE: usize = 8 // start_time
+        + SeasonParams::SPACE // params
+        + 32 * GAME_MODE_COUNT; // leaderboards
+}
+
+/// Seasons the maintainer queued ahead, started in order by
+/// `crank_next_season`.
+#[account]
+pub struct SeasonScheduleAccount {
+    pub bump: u8,
+    /// Lamports paid from this account to whoever cranks a season
+    pub crank_reward: u64,
+    /// Next season first
+    pub seasons: Vec<ScheduledSeason>,
+}
+
+impl Space for SeasonScheduleAccount {
+    const INIT_SPACE: usize = 8 // Account discriminator added by Anchor for each account
+        + 1 // bump
+        + 8 // crank_reward
+        + 4 // length of the seasons vector
+        + MAX_SCHEDULED_SEASONS as usize * ScheduledSeason::SPACE;
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::error::FomoLoveErrorCode;
+
+/// Per mille chances that a spawn is a special tile, see `fomolove_engine::tile`.
+/// All zero plays vanilla 2048.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct SpecialTileRules {
+    /// Immovable tile that vanishes after `blocker_lifetime` moves
+    pub blocker_chance: u16,
+    /// Merges with any tile
+    pub wildcard_chance: u16,
+    /// Doubles the score of the merge it takes part in
+    pub multiplier_chance: u16,
+    pub blocker_lifetime: u8,
+}
+
+impl SpecialTileRules {
+    pub const SPACE: usize = 2 // blocker_chance
+        + 2 // wildcard_chance
+        + 2 // multiplier_chance
+        + 1; // blocker_lifetime
+
+    pub fn validate(&self) -> Result<()> {
+        require!(
+            fomolove_engine::SpecialTiles::from(*self).is_valid(),
+            FomoLoveErrorCode::InvalidSpecialTiles
+        );
+        Ok(())
+    }
+}
+
+impl From<SpecialTileRules> for fomolove_engine::SpecialTiles {
+    fn from(rules: SpecialTileRules) -> Self {
+        fomolove_engine::SpecialTiles {
+            blocker_chance: rules.blocker_chance,
+            wildcard_chance: rules.wildcard_chance,
+            multiplier_chance: rules.multiplier_chance,
+            blocker_lifetime: rules.blocker_lifetime,
+        }
+    }
+}
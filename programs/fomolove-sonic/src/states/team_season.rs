@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{TeamType, TopGame};
+
+/// Standings of a team in one season, created by the first game of the team
+/// registered in the season. `finalize_season` decides the winning team from
+/// these totals alone.
+#[account]
+pub struct TeamSeasonAccount {
+    pub bump: u8,
+    pub season_id: u32,
+    pub team: TeamType,
+    pub game_played: u64,
+    /// Sum of the scores of every game of the team, updated as they are played
+    pub total_score: u64,
+    /// Games that reached the 2048 tile
+    pub winning_game_count: u32,
+    /// Games submitted to a season leaderboard
+    pub submitted_game_count: u32,
+    /// Highest scoring game, default until a game scores
+    pub best_game: TopGame,
+}
+
+impl Space for TeamSeasonAccount {
+    const INIT_SPACE: usize = 8 // Account discriminator added by Anchor for each account
+        + 1 // bump
+        + 4 // season_id
+        + 1 // team
+        + 8 // game_played
+        + 8 // total_score
+        + 4 // winning_game_count
+        + 4 // submitted_game_count
+        + TopGame::SPACE; // best_game
+}
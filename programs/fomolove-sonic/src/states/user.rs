@@ -4,6 +4,7 @@ use crate::TeamType;
 
 #[account]
 pub struct UserAccount {
+  /// 0 for teams chosen before it was stored, constraints derive the bump
   pub bump: u8,
   pub team: TeamType,
 }
@@ -2,17 +2,36 @@ use anchor_lang::prelude::*;
 
 #[account]
 pub struct UserSeasonAccount {
+  /// May be 0 on old accounts, seeds constraints must derive the bump
   pub bump: u8,
-  pub season_id: u8,
-  pub hightest_score: u32,
+  pub season_id: u32,
+  /// Best score of the season, kept across games
+  pub hightest_score: u64,
   pub game_played: u64,
+  /// Real value of the best tile of the season
+  pub best_tile: u32,
+  pub total_moves: u64,
+  pub total_merges: u64,
+  /// Games that reached the 2048 tile
+  pub winning_game_count: u32,
+  /// Games submitted to a season leaderboard
+  pub submitted_game_count: u32,
+  pub first_played_at: u64,
+  pub last_played_at: u64,
 }
 
 impl Space for UserSeasonAccount {
     const INIT_SPACE: usize = 8 // Account discriminator added by Anchor for each account
         + 1 // bump
-        + 1 // season_id
-        + 4 // hightest score
-        + 8; // game_played
+        + 4 // season_id
+        + 8 // hightest score
+        + 8 // game_played
+        + 4 // best_tile
+        + 8 // total_moves
+        + 8 // total_merges
+        + 4 // winning_game_count
+        + 4 // submitted_game_count
+        + 8 // first_played_at
+        + 8; // last_played_at
 
-}
\ No newline at end of file
+}
@@ -6,9 +6,9 @@ use crate::{TeamType, DEFAULT_MAX_WINNER_COUNT};
 pub struct WinnerTopGame {
     pub user: Pubkey,
     pub team: TeamType,
-    pub season_id: u8,
-    pub score: u32,
-    pub top_tile: u16,
+    pub season_id: u32,
+    pub score: u64,
+    pub top_tile: u32,
 }
 
 #[account]
@@ -26,8 +26,8 @@ impl Space for WinnerAccount {
       + DEFAULT_MAX_WINNER_COUNT as usize * (
             32 // user
           + 1
-          + 1 // season_id
-          + 4 // score
-          + 2 // top_tile
+          + 4 // season_id
+          + 8 // score
+          + 4 // top_tile
       );
 }
@@ -1,13 +1,57 @@
 use anchor_lang::prelude::*;
+use solana_program::program::invoke_signed;
+use spl_token_metadata_interface::state::Field;
 
 use crate::{error::FomoLoveErrorCode, SeasonAccount};
 
 pub fn check_season_ended(season_account: &SeasonAccount) -> Result<()> {
     let now = Clock::get()?.unix_timestamp as u64;
 
+    require!(!season_account.finalized, FomoLoveErrorCode::SeasonFinalized);
+    require!(season_account.paused_at == 0, FomoLoveErrorCode::SeasonPaused);
     require!(
         season_account.started_at <= now && season_account.ended_at >= now,
         FomoLoveErrorCode::SeasonEnded
     );
     Ok(())
 }
+
+/// Reads the most recent hash from the SlotHashes sysvar without deserializing
+/// the whole (very large) account. The hash is already public when the
+/// transaction is built, so it must not be treated as unpredictable.
+pub fn recent_slot_hash(slot_hashes: &AccountInfo) -> Result<[u8; 32]> {
+    let data = slot_hashes.try_borrow_data()?;
+
+    // u64 entry count, then (slot: u64, hash: [u8; 32]) entries, newest first
+    require!(data.len() >= 8 + 8 + 32, FomoLoveErrorCode::InvalidSlotHashes);
+
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&data[16..48]);
+    Ok(hash)
+}
+
+/// Updates a field of the game NFT metadata, signed by the config PDA which is
+/// the metadata update authority.
+pub fn update_nft_metadata_field<'info>(
+    nft_mint: &AccountInfo<'info>,
+    config_account: &AccountInfo<'info>,
+    config_bump: u8,
+    field: Field,
+    value: String,
+) -> Result<()> {
+    let seeds = b"config";
+    let signer: &[&[&[u8]]] = &[&[seeds, &[config_bump]]];
+
+    invoke_signed(
+        &spl_token_metadata_interface::instruction::update_field(
+            &spl_token_2022::id(),
+            nft_mint.key,
+            config_account.key,
+            field,
+            value,
+        ),
+        &[nft_mint.clone(), config_account.clone()],
+        signer,
+    )?;
+    Ok(())
+}
@@ -0,0 +1,209 @@
+//! ECVRF-EDWARDS25519-SHA512-TAI proof verification (RFC 9381, suite 0x03)
+//! on top of the curve25519 syscalls.
+//!
+//! The VRF key pair is an ordinary ed25519 key pair, so the oracle proves with
+//! the secret key of the Solana keypair whose public key is in `ConfigAccount`.
+
+use sha2::{Digest, Sha512};
+use solana_zk_token_sdk::curve25519::{
+    edwards::{multiply_edwards, subtract_edwards, validate_edwards, PodEdwardsPoint},
+    scalar::PodScalar,
+};
+
+/// Gamma (32 bytes) || c (16 bytes) || s (32 bytes)
+pub const VRF_PROOF_LEN: usize = 80;
+
+const SUITE: u8 = 0x03;
+
+const BASEPOINT: PodEdwardsPoint = PodEdwardsPoint([
+    0x58, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+    0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+]);
+
+const IDENTITY: PodEdwardsPoint = PodEdwardsPoint([
+    1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+]);
+
+const COFACTOR: PodScalar = PodScalar([
+    8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+]);
+
+/// Order of the prime subgroup, little endian.
+const GROUP_ORDER: [u8; 32] = [
+    0xed, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde, 0x14,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x10,
+];
+
+/// Verifies `proof` for `alpha` under `public_key` and returns the 64 byte
+/// VRF output, or `None` if the proof is invalid.
+pub fn ecvrf_verify(public_key: &[u8; 32], alpha: &[u8], proof: &[u8; VRF_PROOF_LEN]) -> Option<[u8; 64]> {
+    let y = PodEdwardsPoint(*public_key);
+    let gamma = PodEdwardsPoint(proof[..32].try_into().ok()?);
+    let mut c = PodScalar::default();
+    c.0[..16].copy_from_slice(&proof[32..48]);
+    let s = PodScalar(proof[48..].try_into().ok()?);
+
+    if !validate_edwards(&y) || !validate_edwards(&gamma) || !is_canonical_scalar(&s) {
+        return None;
+    }
+    // a small order key would let the oracle choose the output
+    if multiply_edwards(&COFACTOR, &y)? == IDENTITY {
+        return None;
+    }
+
+    let h = hash_to_curve(&y, alpha)?;
+    let u = subtract_edwards(&multiply_edwards(&s, &BASEPOINT)?, &multiply_edwards(&c, &y)?)?;
+    let v = subtract_edwards(&multiply_edwards(&s, &h)?, &multiply_edwards(&c, &gamma)?)?;
+
+    if challenge(&y, &h, &gamma, &u, &v)[..16] != proof[32..48] {
+        return None;
+    }
+
+    let cofactor_gamma = multiply_edwards(&COFACTOR, &gamma)?;
+    Some(
+        Sha512::new()
+            .chain_update([SUITE, 0x03])
+            .chain_update(cofactor_gamma.0)
+            .chain_update([0x00])
+            .finalize()
+            .into(),
+    )
+}
+
+/// Try-and-increment encoding of `alpha` to a point of the prime subgroup.
+fn hash_to_curve(public_key: &PodEdwardsPoint, alpha: &[u8]) -> Option<PodEdwardsPoint> {
+    (0..=u8::MAX).find_map(|counter| {
+        let hash = Sha512::new()
+            .chain_update([SUITE, 0x01])
+            .chain_update(public_key.0)
+            .chain_update(alpha)
+            .chain_update([counter, 0x00])
+            .finalize();
+
+        let candidate = PodEdwardsPoint(hash[..32].try_into().ok()?);
+        if validate_edwards(&candidate) {
+            multiply_edwards(&COFACTOR, &candidate)
+        } else {
+            None
+        }
+    })
+}
+
+fn challenge(
+    y: &PodEdwardsPoint,
+    h: &PodEdwardsPoint,
+    gamma: &PodEdwardsPoint,
+    u: &PodEdwardsPoint,
+    v: &PodEdwardsPoint,
+) -> [u8; 64] {
+    Sha512::new()
+        .chain_update([SUITE, 0x02])
+        .chain_update(y.0)
+        .chain_update(h.0)
+        .chain_update(gamma.0)
+        .chain_update(u.0)
+        .chain_update(v.0)
+        .chain_update([0x00])
+        .finalize()
+        .into()
+}
+
+fn is_canonical_scalar(scalar: &PodScalar) -> bool {
+    scalar.0.iter().rev().cmp(GROUP_ORDER.iter().rev()) == std::cmp::Ordering::Less
+}
+
+/// Reference prover for tests, following RFC 9381 section 5.1.
+#[cfg(test)]
+pub(crate) fn prove(secret_key: &[u8; 32], alpha: &[u8]) -> ([u8; 32], [u8; VRF_PROOF_LEN]) {
+    use curve25519_dalek::{constants::ED25519_BASEPOINT_TABLE, edwards::CompressedEdwardsY, scalar::Scalar};
+
+    let expanded = Sha512::digest(secret_key);
+    let mut x_bytes = [0u8; 32];
+    x_bytes.copy_from_slice(&expanded[..32]);
+    x_bytes[0] &= 248;
+    x_bytes[31] &= 127;
+    x_bytes[31] |= 64;
+    let x = Scalar::from_bytes_mod_order(x_bytes);
+
+    let y = PodEdwardsPoint((&x * &ED25519_BASEPOINT_TABLE).compress().to_bytes());
+    let h = hash_to_curve(&y, alpha).unwrap();
+    let h_point = CompressedEdwardsY(h.0).decompress().unwrap();
+    let gamma = PodEdwardsPoint((x * h_point).compress().to_bytes());
+
+    let nonce: [u8; 64] = Sha512::new().chain_update(&expanded[32..]).chain_update(h.0).finalize().into();
+    let k = Scalar::from_bytes_mod_order_wide(&nonce);
+    let u = PodEdwardsPoint((&k * &ED25519_BASEPOINT_TABLE).compress().to_bytes());
+    let v = PodEdwardsPoint((k * h_point).compress().to_bytes());
+
+    let mut c = [0u8; 32];
+    c[..16].copy_from_slice(&challenge(&y, &h, &gamma, &u, &v)[..16]);
+    let s = k + Scalar::from_bytes_mod_order(c) * x;
+
+    let mut proof = [0u8; VRF_PROOF_LEN];
+    proof[..32].copy_from_slice(&gamma.0);
+    proof[32..48].copy_from_slice(&c[..16]);
+    proof[48..].copy_from_slice(s.as_bytes());
+    (y.0, proof)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex<const N: usize>(value: &str) -> [u8; N] {
+        let mut bytes = [0u8; N];
+        for (index, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&value[index * 2..index * 2 + 2], 16).unwrap();
+        }
+        bytes
+    }
+
+    #[test]
+    fn matches_the_rfc_test_vector() {
+        let secret_key = hex::<32>("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60");
+        let (public_key, proof) = prove(&secret_key, &[]);
+
+        assert_eq!(public_key, hex::<32>("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"));
+        assert_eq!(
+            proof,
+            hex::<80>(
+                "8657106690b5526245a92b003bb079ccd1a92130477671f6fc01ad16f26f723f26f8a57ccaed74ee1b190bed1f479d9727d2d0f9b005a6e456a35d4fb0daab1268a1b0db10836d9826a528ca76567805"
+            )
+        );
+        assert_eq!(
+            ecvrf_verify(&public_key, &[], &proof),
+            Some(hex::<64>(
+                "90cf1df3b703cce59e2a35b925d411164068269d7b2d29f3301c03dd757876ff66b71dda49d2de59d03450451af026798e8f81cd2e333de5cdf4f3e140fdd8ae"
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_a_tampered_proof() {
+        let (public_key, proof) = prove(&[7; 32], b"alpha");
+        assert!(ecvrf_verify(&public_key, b"alpha", &proof).is_some());
+
+        for index in [0, 32, 79] {
+            let mut tampered = proof;
+            tampered[index] ^= 1;
+            assert_eq!(ecvrf_verify(&public_key, b"alpha", &tampered), None);
+        }
+    }
+
+    #[test]
+    fn rejects_another_alpha_or_key() {
+        let (public_key, proof) = prove(&[7; 32], b"alpha");
+        let (other_key, _) = prove(&[8; 32], b"alpha");
+
+        assert_eq!(ecvrf_verify(&public_key, b"beta", &proof), None);
+        assert_eq!(ecvrf_verify(&other_key, b"alpha", &proof), None);
+        assert_eq!(ecvrf_verify(&IDENTITY.0, b"alpha", &proof), None);
+    }
+
+    #[test]
+    fn rejects_a_non_canonical_response() {
+        let (public_key, mut proof) = prove(&[7; 32], b"alpha");
+        proof[48..].copy_from_slice(&GROUP_ORDER);
+        assert_eq!(ecvrf_verify(&public_key, b"alpha", &proof), None);
+    }
+}
@@ -1,9 +1,9 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
-import { Connection, PublicKey } from "@solana/web3.js";
+import { Connection, Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
 import { Ctx, createCtx } from "./helpers/ctx";
 import { CheckCtx } from "./helpers/check";
-import { sleep } from "./helpers/helper";
+import { seasonIdSeed, sleep } from "./helpers/helper";
 import { ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID, getAssociatedTokenAddressSync, getTokenMetadata } from "@solana/spl-token";
 import { ASSOCIATED_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/utils/token";
 import { assert, expect } from "chai";
@@ -13,6 +13,56 @@ import { FomoloveSonic } from "../target/types/fomolove_sonic";
 const MEME_TEAM_URL = 'https://bafybeidlf73itmw6hzskpy7amdcjzww3umwmvlwiqubbs2mkll2tnv7ojq.ipfs.nftstorage.link/me';
 const CHAIN_TEAM_URL = 'https://bafybeiferm3u2nsdnzcf25ubqdy3qjbn3bu6meeeue52lrkvlr3llqbpce.ipfs.nftstorage.link/chain';
 
+// Classic, MoveLimited and Sprint games, TimeAttack is left disabled
+const MODE_RULES = {
+  enabledModes: 0b1101,
+  timeLimit: new anchor.BN(0),
+  moveLimit: 500,
+  sprintTargetTile: 128,
+};
+
+const NO_SPECIAL_TILES = {
+  blockerChance: 0,
+  wildcardChance: 0,
+  multiplierChance: 0,
+  blockerLifetime: 0,
+};
+
+// Per mille chances of blockers, wildcards and x2 tiles
+const SPECIAL_TILES = {
+  blockerChance: 50,
+  wildcardChance: 50,
+  multiplierChance: 100,
+  blockerLifetime: 8,
+};
+
+// Percent bonuses for multi-merge moves and runs of merging moves
+const SCORING = {
+  comboBonus: 50,
+  streakBonus: 10,
+  streakCap: 5,
+};
+
+const ENTRY_FEE = 1_000_000;
+// Lamports paid to whoever cranks a scheduled season
+const CRANK_REWARD = 100_000;
+
+// Duration 0 keeps the season duration of the config account
+const SEASON_PARAMS = {
+  duration: new anchor.BN(0),
+  leaderboardSize: 10,
+  entriesPerPlayer: 1,
+  minScore: new anchor.BN(0),
+  minTile: 2,
+  entryFee: new anchor.BN(ENTRY_FEE),
+  boardSize: 4,
+  modeRules: MODE_RULES,
+  specialTiles: NO_SPECIAL_TILES,
+  scoring: SCORING,
+};
+
+const SPECIAL_SEASON_PARAMS = { ...SEASON_PARAMS, specialTiles: SPECIAL_TILES };
+
 describe("fomolove-sonic", () => {
   anchor.setProvider(anchor.AnchorProvider.env());
 
@@ -20,6 +70,79 @@ describe("fomolove-sonic", () => {
   const connection = new Connection("http://localhost:8899", 'confirmed');
 
   let ctx: Ctx;
+  // Leaderboards queued with each scheduled season, indexed by game mode
+  let scheduledLeaderboards: PublicKey[][] = [];
+  // Daily challenge game of user1 left active until the season ends
+  let unsubmittedGame: PublicKey;
+
+  // Registers a game of user1 on a fresh mint, returns the mint and game PDA
+  async function registerExtraGame(mode, dailyChallenge: PublicKey | null): Promise<[Keypair, PublicKey]> {
+    const nftMint = Keypair.generate();
+    const gameAccount = PublicKey.findProgramAddressSync(
+      [Buffer.from("game"), nftMint.publicKey.toBuffer()],
+      program.programId
+    )[0];
+    const gameLogAccount = PublicKey.findProgramAddressSync(
+      [Buffer.from("game_log"), gameAccount.toBuffer()],
+      program.programId
+    )[0];
+
+    await program.methods.registerGame(mode).accountsPartial({
+      user: ctx.user1.publicKey,
+      userAccount: ctx.user1Account,
+      userSeasonAccount: ctx.user1SeasonAccount,
+      teamSeasonAccount: ctx.memeTeamSeasonAccount,
+      gameAccount,
+      gameLogAccount,
+      seasonAccount: ctx.seasonAccount,
+      dailyChallenge,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
+      nftMint: nftMint.publicKey,
+      configAccount: ctx.configAccount,
+      tokenAccount: getAssociatedTokenAddressSync(
+        nftMint.publicKey,
+        ctx.user1.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID,
+        ASSOCIATED_TOKEN_PROGRAM_ID
+      ),
+      systemProgram: anchor.web3.SystemProgram.programId,
+    }).signers([ctx.user1, nftMint]).rpc();
+
+    return [nftMint, gameAccount];
+  }
+
+  // Creates the empty leaderboard accounts the crank initializes for a
+  // scheduled season, the default key for the modes it does not enable
+  async function createScheduledLeaderboards(params): Promise<PublicKey[]> {
+    const space = 64 + params.leaderboardSize * 88;
+    const lamports = await connection.getMinimumBalanceForRentExemption(space);
+    const leaderboards: PublicKey[] = [];
+    for (let mode = 0; mode < 4; mode++) {
+      if ((params.modeRules.enabledModes & (1 << mode)) == 0) {
+        leaderboards.push(PublicKey.default);
+        continue;
+      }
+      const leaderboard = Keypair.generate();
+      const tx = new anchor.web3.Transaction().add(SystemProgram.createAccount({
+        fromPubkey: ctx.maintainer.publicKey,
+        newAccountPubkey: leaderboard.publicKey,
+        space,
+        lamports,
+        programId: program.programId,
+      }));
+      await program.provider.sendAndConfirm(tx, [ctx.maintainer, leaderboard]);
+      leaderboards.push(leaderboard.publicKey);
+    }
+    return leaderboards;
+  }
+
+  function leaderboardMetas(leaderboards: PublicKey[], isWritable: boolean) {
+    return leaderboards
+      .filter((leaderboard) => !leaderboard.equals(PublicKey.default))
+      .map((pubkey) => ({ pubkey, isWritable, isSigner: false }));
+  }
 
   it("Is initialized!", async () => {
     ctx = await createCtx(connection, program);
@@ -36,7 +159,7 @@ describe("fomolove-sonic", () => {
 
     const currentSeasonId = configAccount.currentSeasonId;
 
-    const seasonIdBuffer = Buffer.from([currentSeasonId + 1]);
+    const seasonIdBuffer = seasonIdSeed(currentSeasonId + 1);
 
     ctx.seasonAccount = PublicKey.findProgramAddressSync(
       [Buffer.from("season"), seasonIdBuffer],
@@ -47,19 +170,66 @@ describe("fomolove-sonic", () => {
       [Buffer.from("user_season"), ctx.user1.publicKey.toBuffer(), seasonIdBuffer],
       program.programId
     )[0];
+
+    // user1 plays for the meme team, TeamType::MemeTeam is 1
+    ctx.memeTeamSeasonAccount = PublicKey.findProgramAddressSync(
+      [Buffer.from("team_season"), Buffer.from([1]), seasonIdBuffer],
+      program.programId
+    )[0];
+  });
+
+  it("Cannot migrate accounts already using u32 season ids", async () => {
+    try {
+      await program.methods.migrateSeasonIds().accountsPartial({
+        maintainer: ctx.maintainer.publicKey,
+        configAccount: ctx.configAccount,
+        winnerAccount: ctx.winnerAccount,
+      }).signers([ctx.maintainer]).rpc();
+      assert.fail("migrated a current config");
+    } catch (error) {
+      assert.isTrue(error instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = error;
+      expect(err.error.errorCode.code).to.equal('InvalidLegacyAccount')
+    }
   });
 
   it("Create season!", async () => {
     await sleep(3000);
      const startTime = new anchor.BN(Date.now() / 1000);
 
-    const tx = await program.methods.startSeason(startTime).accountsPartial({
+    const tx = await program.methods.startSeason(startTime, SEASON_PARAMS).accountsPartial({
       maintainer: ctx.maintainer.publicKey,
       configAccount: ctx.configAccount,
       seasonAccount: ctx.seasonAccount,
     }).signers([ctx.maintainer]).rpc();
   });
 
+  it("Create the classic leaderboard!", async () => {
+    // Too large for a CPI create, the account is created beforehand
+    const space = 64 + SEASON_PARAMS.leaderboardSize * 88;
+    const createAccount = SystemProgram.createAccount({
+      fromPubkey: ctx.maintainer.publicKey,
+      newAccountPubkey: ctx.classicLeaderboard.publicKey,
+      space,
+      lamports: await connection.getMinimumBalanceForRentExemption(space),
+      programId: program.programId,
+    });
+
+    await program.methods.createSeasonLeaderboard({ classic: {} }).accountsPartial({
+      maintainer: ctx.maintainer.publicKey,
+      configAccount: ctx.configAccount,
+      seasonAccount: ctx.seasonAccount,
+      leaderboard: ctx.classicLeaderboard.publicKey,
+    }).preInstructions([createAccount]).signers([ctx.maintainer, ctx.classicLeaderboard]).rpc();
+
+    const season = await CheckCtx.season(ctx);
+    expect(season.leaderboards[0].toBase58()).to.equal(ctx.classicLeaderboard.publicKey.toBase58());
+
+    const leaderboard = await program.account.seasonLeaderboardAccount.fetch(ctx.classicLeaderboard.publicKey);
+    expect(leaderboard.maxGameCount).to.equal(SEASON_PARAMS.leaderboardSize);
+    expect(leaderboard.entriesPerPlayer).to.equal(SEASON_PARAMS.entriesPerPlayer);
+  });
+
   it("Choose team!", async () => {
     const tx = await program.methods.chooseTeam({ memeTeam: {} }).accountsPartial({
       user: ctx.user1.publicKey,
@@ -95,13 +265,16 @@ describe("fomolove-sonic", () => {
       ASSOCIATED_TOKEN_PROGRAM_ID
     );
     ctx.nftTokenAccount = destinationTokenAccount;
+    const seasonBalance = await connection.getBalance(ctx.seasonAccount);
 
-    const tx = await program.methods.registerGame().accountsPartial({
+    const tx = await program.methods.registerGame({ classic: {} }).accountsPartial({
       user: ctx.user1.publicKey,
       userAccount: ctx.user1Account,
       userSeasonAccount: ctx.user1SeasonAccount,
+      teamSeasonAccount: ctx.memeTeamSeasonAccount,
       gameAccount: ctx.gameAccount,
       seasonAccount: ctx.seasonAccount,
+      dailyChallenge: null,
       tokenProgram: TOKEN_2022_PROGRAM_ID,
       associatedTokenProgram: ASSOCIATED_PROGRAM_ID,
       nftMint: ctx.nftMint.publicKey,
@@ -110,6 +283,21 @@ describe("fomolove-sonic", () => {
       systemProgram: anchor.web3.SystemProgram.programId,
 
     }).signers([ctx.user1, ctx.nftMint]).rpc();
+
+    const season = await program.account.seasonAccount.fetch(ctx.seasonAccount);
+    expect(season.entryFees.toNumber()).to.equal(ENTRY_FEE);
+    expect(await connection.getBalance(ctx.seasonAccount)).to.equal(seasonBalance + ENTRY_FEE);
+  });
+
+  it("Cannot register a game in a mode the season did not enable", async () => {
+    try {
+      await registerExtraGame({ timeAttack: {} }, null);
+      assert.fail("game was registered in a disabled mode");
+    } catch (error) {
+      assert.isTrue(error instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = error;
+      expect(err.error.errorCode.code).to.equal('ModeNotEnabled')
+    }
   });
 
   it("CANNOT Make MOVE if not owner of token", async () => {
@@ -118,9 +306,11 @@ describe("fomolove-sonic", () => {
         user: ctx.user2.publicKey,
         configAccount: ctx.configAccount,
         game: ctx.gameAccount,
+        seasonAccount: ctx.seasonAccount,
         userAccount: ctx.user1Account,
         userTeamAccount: ctx.memeTeamAccount,
         userSeasonAccount: ctx.user1SeasonAccount,
+        teamSeasonAccount: ctx.memeTeamSeasonAccount,
         nftMint: ctx.nftMint.publicKey,
         nftTokenAccount: ctx.nftTokenAccount,
         winnerAccount: ctx.winnerAccount,
@@ -139,9 +329,11 @@ describe("fomolove-sonic", () => {
       user: ctx.user1.publicKey,
       configAccount: ctx.configAccount,
       game: ctx.gameAccount,
+      seasonAccount: ctx.seasonAccount,
       userAccount: ctx.user1Account,
       userTeamAccount: ctx.memeTeamAccount,
       userSeasonAccount: ctx.user1SeasonAccount,
+      teamSeasonAccount: ctx.memeTeamSeasonAccount,
       nftMint: ctx.nftMint.publicKey,
       nftTokenAccount: ctx.nftTokenAccount,
       winnerAccount: ctx.winnerAccount,
@@ -157,9 +349,11 @@ describe("fomolove-sonic", () => {
       user: ctx.user1.publicKey,
       configAccount: ctx.configAccount,
       game: ctx.gameAccount,
+      seasonAccount: ctx.seasonAccount,
       userAccount: ctx.user1Account,
       userTeamAccount: ctx.memeTeamAccount,
       userSeasonAccount: ctx.user1SeasonAccount,
+      teamSeasonAccount: ctx.memeTeamSeasonAccount,
       nftMint: ctx.nftMint.publicKey,
       nftTokenAccount: ctx.nftTokenAccount,
       winnerAccount: ctx.winnerAccount,
@@ -175,9 +369,11 @@ describe("fomolove-sonic", () => {
       user: ctx.user1.publicKey,
       configAccount: ctx.configAccount,
       game: ctx.gameAccount,
+      seasonAccount: ctx.seasonAccount,
       userAccount: ctx.user1Account,
       userTeamAccount: ctx.memeTeamAccount,
       userSeasonAccount: ctx.user1SeasonAccount,
+      teamSeasonAccount: ctx.memeTeamSeasonAccount,
       nftMint: ctx.nftMint.publicKey,
       nftTokenAccount: ctx.nftTokenAccount,
       winnerAccount: ctx.winnerAccount,
@@ -193,9 +389,11 @@ describe("fomolove-sonic", () => {
       user: ctx.user1.publicKey,
       configAccount: ctx.configAccount,
       game: ctx.gameAccount,
+      seasonAccount: ctx.seasonAccount,
       userAccount: ctx.user1Account,
       userTeamAccount: ctx.memeTeamAccount,
       userSeasonAccount: ctx.user1SeasonAccount,
+      teamSeasonAccount: ctx.memeTeamSeasonAccount,
       nftMint: ctx.nftMint.publicKey,
       nftTokenAccount: ctx.nftTokenAccount,
       winnerAccount: ctx.winnerAccount,
@@ -211,9 +409,11 @@ describe("fomolove-sonic", () => {
       user: ctx.user1.publicKey,
       configAccount: ctx.configAccount,
       game: ctx.gameAccount,
+      seasonAccount: ctx.seasonAccount,
       userAccount: ctx.user1Account,
       userTeamAccount: ctx.memeTeamAccount,
       userSeasonAccount: ctx.user1SeasonAccount,
+      teamSeasonAccount: ctx.memeTeamSeasonAccount,
       nftMint: ctx.nftMint.publicKey,
       nftTokenAccount: ctx.nftTokenAccount,
       winnerAccount: ctx.winnerAccount,
@@ -230,9 +430,11 @@ describe("fomolove-sonic", () => {
       user: ctx.user1.publicKey,
       configAccount: ctx.configAccount,
       game: ctx.gameAccount,
+      seasonAccount: ctx.seasonAccount,
       userAccount: ctx.user1Account,
       userTeamAccount: ctx.memeTeamAccount,
       userSeasonAccount: ctx.user1SeasonAccount,
+      teamSeasonAccount: ctx.memeTeamSeasonAccount,
       nftMint: ctx.nftMint.publicKey,
       nftTokenAccount: ctx.nftTokenAccount,
       winnerAccount: ctx.winnerAccount,
@@ -249,9 +451,33 @@ describe("fomolove-sonic", () => {
       user: ctx.user1.publicKey,
       configAccount: ctx.configAccount,
       game: ctx.gameAccount,
+      seasonAccount: ctx.seasonAccount,
+      userAccount: ctx.user1Account,
+      userTeamAccount: ctx.memeTeamAccount,
+      userSeasonAccount: ctx.user1SeasonAccount,
+      teamSeasonAccount: ctx.memeTeamSeasonAccount,
+      nftMint: ctx.nftMint.publicKey,
+      nftTokenAccount: ctx.nftTokenAccount,
+      winnerAccount: ctx.winnerAccount,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      systemProgram: SYSTEM_PROGRAM_ID
+    }).signers([ctx.user1]).rpc();
+
+    await CheckCtx.getGameState(ctx);
+  });
+
+  it("Make moves: batch!", async () => {
+    const before = await ctx.program.account.gameAccount.fetch(ctx.gameAccount);
+
+    await program.methods.makeMoves([{ down: {} }, { right: {} }, { up: {} }, { left: {} }]).accountsPartial({
+      user: ctx.user1.publicKey,
+      configAccount: ctx.configAccount,
+      game: ctx.gameAccount,
+      seasonAccount: ctx.seasonAccount,
       userAccount: ctx.user1Account,
       userTeamAccount: ctx.memeTeamAccount,
       userSeasonAccount: ctx.user1SeasonAccount,
+      teamSeasonAccount: ctx.memeTeamSeasonAccount,
       nftMint: ctx.nftMint.publicKey,
       nftTokenAccount: ctx.nftTokenAccount,
       winnerAccount: ctx.winnerAccount,
@@ -259,9 +485,171 @@ describe("fomolove-sonic", () => {
       systemProgram: SYSTEM_PROGRAM_ID
     }).signers([ctx.user1]).rpc();
 
+    const after = await ctx.program.account.gameAccount.fetch(ctx.gameAccount);
+    expect(after.moveCount).to.be.greaterThan(before.moveCount);
+    expect(after.moveCount - before.moveCount).to.be.at.most(4);
     await CheckCtx.getGameState(ctx);
   });
 
+  it("Cannot make more moves than a batch holds", async () => {
+    try {
+      await program.methods.makeMoves(Array(65).fill({ down: {} })).accountsPartial({
+        user: ctx.user1.publicKey,
+        configAccount: ctx.configAccount,
+        game: ctx.gameAccount,
+        seasonAccount: ctx.seasonAccount,
+        userAccount: ctx.user1Account,
+        userTeamAccount: ctx.memeTeamAccount,
+        userSeasonAccount: ctx.user1SeasonAccount,
+        teamSeasonAccount: ctx.memeTeamSeasonAccount,
+        nftMint: ctx.nftMint.publicKey,
+        nftTokenAccount: ctx.nftTokenAccount,
+        winnerAccount: ctx.winnerAccount,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SYSTEM_PROGRAM_ID
+      }).signers([ctx.user1]).rpc();
+      assert.fail("made more moves than a batch holds");
+    } catch (error) {
+      assert.isTrue(error instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = error;
+      expect(err.error.errorCode.code).to.equal('TooManyMoves')
+    }
+  });
+
+  it("Game tracks its merge streak", async () => {
+    const game = await ctx.program.account.gameAccount.fetch(ctx.gameAccount);
+
+    expect(game.scoring.comboBonus).to.equal(SCORING.comboBonus);
+    expect(game.streak).to.be.at.most(game.bestStreak);
+    expect(game.bestStreak).to.be.at.most(game.moveCount);
+    expect(game.bestCombo).to.be.at.most(game.boardSize * game.boardSize / 2);
+  });
+
+  it("Team standings follow the games of the season", async () => {
+    const game = await ctx.program.account.gameAccount.fetch(ctx.gameAccount);
+    const standings = await ctx.program.account.teamSeasonAccount.fetch(ctx.memeTeamSeasonAccount);
+
+    expect(standings.team).to.deep.equal({ memeTeam: {} });
+    expect(standings.gamePlayed.toNumber()).to.equal(1);
+    expect(standings.totalScore.toNumber()).to.equal(game.score.toNumber());
+    expect(standings.bestGame.game.toBase58()).to.equal(ctx.gameAccount.toBase58());
+    expect(standings.submittedGameCount).to.equal(0);
+  });
+
+  it("User season stats follow the games of the season", async () => {
+    const game = await ctx.program.account.gameAccount.fetch(ctx.gameAccount);
+    const stats = await ctx.program.account.userSeasonAccount.fetch(ctx.user1SeasonAccount);
+
+    expect(stats.gamePlayed.toNumber()).to.equal(1);
+    expect(stats.hightestScore.toNumber()).to.equal(game.score.toNumber());
+    expect(stats.bestTile).to.equal(game.topTile);
+    expect(stats.totalMoves.toNumber()).to.equal(game.moveCount);
+    expect(stats.totalMerges.toNumber()).to.be.at.least(game.bestCombo);
+    expect(stats.firstPlayedAt.toNumber()).to.be.greaterThan(0);
+    expect(stats.lastPlayedAt.toNumber()).to.be.at.least(stats.firstPlayedAt.toNumber());
+  });
+
+  it("Paused seasons reject moves and resume later", async () => {
+    const before = await CheckCtx.season(ctx);
+    await program.methods.pauseSeason().accountsPartial({
+      maintainer: ctx.maintainer.publicKey,
+      configAccount: ctx.configAccount,
+      seasonAccount: ctx.seasonAccount,
+    }).signers([ctx.maintainer]).rpc();
+
+    try {
+      await program.methods.makeMove({ down: {} }).accountsPartial({
+        user: ctx.user1.publicKey,
+        configAccount: ctx.configAccount,
+        game: ctx.gameAccount,
+        seasonAccount: ctx.seasonAccount,
+        userAccount: ctx.user1Account,
+        userTeamAccount: ctx.memeTeamAccount,
+        userSeasonAccount: ctx.user1SeasonAccount,
+        teamSeasonAccount: ctx.memeTeamSeasonAccount,
+        nftMint: ctx.nftMint.publicKey,
+        nftTokenAccount: ctx.nftTokenAccount,
+        winnerAccount: ctx.winnerAccount,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SYSTEM_PROGRAM_ID
+      }).signers([ctx.user1]).rpc();
+      assert.fail("moved in a paused season");
+    } catch (error) {
+      assert.isTrue(error instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = error;
+      expect(err.error.errorCode.code).to.equal('SeasonPaused')
+    }
+
+    await sleep(1000);
+    await program.methods.resumeSeason().accountsPartial({
+      maintainer: ctx.maintainer.publicKey,
+      configAccount: ctx.configAccount,
+      seasonAccount: ctx.seasonAccount,
+    }).signers([ctx.maintainer]).rpc();
+
+    const after = await CheckCtx.season(ctx);
+    expect(after.pausedAt.toNumber()).to.equal(0);
+    expect(after.endedAt.gt(before.endedAt)).to.be.true;
+    expect((await CheckCtx.config(ctx)).currentSeasonEndedAt.toString()).to.equal(after.endedAt.toString());
+  });
+
+  it("Extend the season!", async () => {
+    const season = await CheckCtx.season(ctx);
+    const endedAt = season.endedAt.add(new anchor.BN(60));
+    await program.methods.updateSeasonEnd(endedAt).accountsPartial({
+      maintainer: ctx.maintainer.publicKey,
+      configAccount: ctx.configAccount,
+      seasonAccount: ctx.seasonAccount,
+    }).signers([ctx.maintainer]).rpc();
+
+    expect((await CheckCtx.season(ctx)).endedAt.toNumber()).to.equal(endedAt.toNumber());
+    expect((await CheckCtx.config(ctx)).currentSeasonEndedAt.toNumber()).to.equal(endedAt.toNumber());
+  });
+
+  it("Cannot end the season in the past", async () => {
+    try {
+      await program.methods.updateSeasonEnd(new anchor.BN(1)).accountsPartial({
+        maintainer: ctx.maintainer.publicKey,
+        configAccount: ctx.configAccount,
+        seasonAccount: ctx.seasonAccount,
+      }).signers([ctx.maintainer]).rpc();
+      assert.fail("ended the season in the past");
+    } catch (error) {
+      assert.isTrue(error instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = error;
+      expect(err.error.errorCode.code).to.equal('InvalidSeasonEnd')
+    }
+  });
+
+  it("Move log records every move", async () => {
+    const game = await ctx.program.account.gameAccount.fetch(ctx.gameAccount);
+    const gameLog = await CheckCtx.gameLog(ctx);
+
+    expect(gameLog.moveCount).to.equal(game.moveCount);
+    expect(gameLog.moves.length).to.equal(Math.ceil(game.moveCount / 4));
+  });
+
+  it("Cannot fulfill spawn when no spawn is pending", async () => {
+    try {
+      await program.methods.fulfillSpawn(Array(80).fill(0)).accountsPartial({
+        configAccount: ctx.configAccount,
+        userAccount: ctx.user1Account,
+        winnerAccount: ctx.winnerAccount,
+        userTeamAccount: ctx.memeTeamAccount,
+        userSeasonAccount: ctx.user1SeasonAccount,
+        teamSeasonAccount: ctx.memeTeamSeasonAccount,
+        game: ctx.gameAccount,
+        nftMint: ctx.nftMint.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }).rpc();
+      assert.fail("fulfilled a spawn that was not pending");
+    } catch (error) {
+      assert.isTrue(error instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = error;
+      expect(err.error.errorCode.code).to.equal('NoPendingSpawn')
+    }
+  });
+
   it("Sumit to leaderboard", async () => {
     const tx = await program.methods.submitLeaderboard().accountsPartial({
       user: ctx.user1.publicKey,
@@ -269,24 +657,211 @@ describe("fomolove-sonic", () => {
       seasonAccount: ctx.seasonAccount,
       userAccount: ctx.user1Account,
       gameAccount: ctx.gameAccount,
+      leaderboard: ctx.classicLeaderboard.publicKey,
+      leaderboardPlayer: ctx.user1ClassicEntries,
+      teamSeasonAccount: ctx.memeTeamSeasonAccount,
+      userSeasonAccount: ctx.user1SeasonAccount,
       nftMint: ctx.nftMint.publicKey,
       systemProgram: SYSTEM_PROGRAM_ID
     }).signers([ctx.user1]).rpc()  ;
 
-    const season = await CheckCtx.season(ctx);
-    console.log("Season Leader board: ", season.leaderboard);
+    const classic = await CheckCtx.seasonLeaderboardGames(ctx, ctx.classicLeaderboard.publicKey);
+    expect(classic.map((game) => game.game.toBase58())).to.include(ctx.gameAccount.toBase58());
+    const standings = await program.account.teamSeasonAccount.fetch(ctx.memeTeamSeasonAccount);
+    expect(standings.submittedGameCount).to.equal(1);
+    const stats = await program.account.userSeasonAccount.fetch(ctx.user1SeasonAccount);
+    expect(stats.submittedGameCount).to.equal(1);
+    const entries = await program.account.leaderboardPlayerAccount.fetch(ctx.user1ClassicEntries);
+    expect(entries.entries.map((game) => game.game.toBase58())).to.deep.equal([ctx.gameAccount.toBase58()]);
+    console.log("Classic leader board: ", classic);
     await sleep(2000)
     const nftMedatadata = await getTokenMetadata(connection, ctx.nftMint.publicKey, 'confirmed', TOKEN_2022_PROGRAM_ID);
     console.log("🚀 ~ nftMedatadata: ", nftMedatadata);
   });
 
+  it("Cannot submit the same game twice", async () => {
+    try {
+      await program.methods.submitLeaderboard().accountsPartial({
+        user: ctx.user1.publicKey,
+        configAccount: ctx.configAccount,
+        seasonAccount: ctx.seasonAccount,
+        userAccount: ctx.user1Account,
+        gameAccount: ctx.gameAccount,
+        leaderboard: ctx.classicLeaderboard.publicKey,
+        leaderboardPlayer: ctx.user1ClassicEntries,
+        teamSeasonAccount: ctx.memeTeamSeasonAccount,
+        userSeasonAccount: ctx.user1SeasonAccount,
+        nftMint: ctx.nftMint.publicKey,
+        systemProgram: SYSTEM_PROGRAM_ID
+      }).signers([ctx.user1]).rpc();
+      assert.fail("game was submitted twice");
+    } catch (error) {
+      assert.isTrue(error instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = error;
+      expect(err.error.errorCode.code).to.equal('GameAlreadySubmitted')
+    }
+  });
+
+  it("Create daily challenge!", async () => {
+    const dayBuffer = Buffer.alloc(4);
+    dayBuffer.writeUInt32LE(0);
+    const season = await CheckCtx.season(ctx);
+    ctx.dailyChallenge = PublicKey.findProgramAddressSync(
+      [Buffer.from("daily_challenge"), seasonIdSeed(season.seasonId), dayBuffer],
+      program.programId
+    )[0];
+
+    await program.methods.createDailyChallenge(0).accountsPartial({
+      payer: ctx.user1.publicKey,
+      seasonAccount: ctx.seasonAccount,
+      dailyChallenge: ctx.dailyChallenge,
+    }).signers([ctx.user1]).rpc();
+  });
+
+  it("Daily challenge games open on the same tiles", async () => {
+    const [, firstGame] = await registerExtraGame({ classic: {} }, ctx.dailyChallenge);
+    const [secondMint, secondGame] = await registerExtraGame({ classic: {} }, ctx.dailyChallenge);
+
+    const first = await program.account.gameAccount.fetch(firstGame);
+    const second = await program.account.gameAccount.fetch(secondGame);
+    expect(Buffer.from(first.board).equals(Buffer.from(second.board))).to.be.true;
+    expect(first.dailyChallenge.toBase58()).to.equal(ctx.dailyChallenge.toBase58());
+
+    try {
+      await program.methods.submitLeaderboard().accountsPartial({
+        user: ctx.user1.publicKey,
+        configAccount: ctx.configAccount,
+        seasonAccount: ctx.seasonAccount,
+        userAccount: ctx.user1Account,
+        gameAccount: secondGame,
+        leaderboard: ctx.classicLeaderboard.publicKey,
+        leaderboardPlayer: ctx.user1ClassicEntries,
+        teamSeasonAccount: ctx.memeTeamSeasonAccount,
+        userSeasonAccount: ctx.user1SeasonAccount,
+        nftMint: secondMint.publicKey,
+        systemProgram: SYSTEM_PROGRAM_ID
+      }).signers([ctx.user1]).rpc();
+      assert.fail("challenge game was submitted to the season leaderboard");
+    } catch (error) {
+      assert.isTrue(error instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = error;
+      expect(err.error.errorCode.code).to.equal('DailyChallengeGame')
+    }
+  });
+
+  it("Cannot submit a season game to a daily challenge", async () => {
+    try {
+      await program.methods.submitDailyChallenge().accountsPartial({
+        user: ctx.user1.publicKey,
+        userAccount: ctx.user1Account,
+        configAccount: ctx.configAccount,
+        seasonAccount: ctx.seasonAccount,
+        dailyChallenge: ctx.dailyChallenge,
+        gameAccount: ctx.gameAccount,
+        nftMint: ctx.nftMint.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }).signers([ctx.user1]).rpc();
+      assert.fail("season game was submitted to a daily challenge");
+    } catch (error) {
+      assert.isTrue(error instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = error;
+      expect(err.error.errorCode.code).to.equal('ChallengeMismatch')
+    }
+  });
+
+  it("Cannot submit a daily challenge game under another player's team", async () => {
+    await program.methods.chooseTeam({ chainTeam: {} }).accountsPartial({
+      user: ctx.user2.publicKey,
+      userAccount: ctx.user2Account,
+      teamMemeAccount: ctx.memeTeamAccount,
+      teamChainAccount: ctx.chainTeamAccount
+    }).signers([ctx.user2]).rpc();
+    const [nftMint, gameAccount] = await registerExtraGame({ classic: {} }, ctx.dailyChallenge);
+    unsubmittedGame = gameAccount;
+
+    try {
+      await program.methods.submitDailyChallenge().accountsPartial({
+        user: ctx.user1.publicKey,
+        userAccount: ctx.user2Account,
+        configAccount: ctx.configAccount,
+        seasonAccount: ctx.seasonAccount,
+        dailyChallenge: ctx.dailyChallenge,
+        gameAccount,
+        nftMint: nftMint.publicKey,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      }).signers([ctx.user1]).rpc();
+      assert.fail("submitted a daily challenge game under another player's team");
+    } catch (error) {
+      assert.isTrue(error instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = error;
+      expect(err.error.errorCode.code).to.equal('ConstraintSeeds')
+    }
+  });
+
+  it("Cannot finalize a season before it ends", async () => {
+    const season = await CheckCtx.season(ctx);
+    const seasonResult = PublicKey.findProgramAddressSync(
+      [Buffer.from("season_result"), seasonIdSeed(season.seasonId)],
+      program.programId
+    )[0];
+
+    const chainTeamSeason = PublicKey.findProgramAddressSync(
+      [Buffer.from("team_season"), Buffer.from([2]), seasonIdSeed(season.seasonId)],
+      program.programId
+    )[0];
+
+    try {
+      await program.methods.finalizeSeason().accountsPartial({
+        payer: ctx.user2.publicKey,
+        seasonAccount: ctx.seasonAccount,
+        seasonResult,
+        memeTeamSeason: ctx.memeTeamSeasonAccount,
+        chainTeamSeason,
+      }).remainingAccounts([
+        { pubkey: ctx.classicLeaderboard.publicKey, isWritable: false, isSigner: false },
+      ]).signers([ctx.user2]).rpc();
+      assert.fail("finalized an active season");
+    } catch (error) {
+      assert.isTrue(error instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = error;
+      expect(err.error.errorCode.code).to.equal('SeasonNotEnded')
+    }
+
+    expect((await CheckCtx.season(ctx)).finalized).to.equal(false);
+  });
+
+  it("Cannot withdraw entry fees before the season is finalized", async () => {
+    const season = await CheckCtx.season(ctx);
+    const seasonResult = PublicKey.findProgramAddressSync(
+      [Buffer.from("season_result"), seasonIdSeed(season.seasonId)],
+      program.programId
+    )[0];
+
+    try {
+      await program.methods.withdrawEntryFees().accountsPartial({
+        maintainer: ctx.maintainer.publicKey,
+        configAccount: ctx.configAccount,
+        seasonAccount: ctx.seasonAccount,
+        seasonResult,
+        recipient: ctx.maintainer.publicKey,
+      }).signers([ctx.maintainer]).rpc();
+      assert.fail("withdrew the fees of an active season");
+    } catch (error) {
+      assert.isTrue(error instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = error;
+      expect(err.error.errorCode.code).to.equal('AccountNotInitialized')
+    }
+
+    expect((await CheckCtx.season(ctx)).entryFees.toNumber()).to.be.greaterThan(0);
+  });
+
   it("Cannot create season if not ended", async () => {
     await sleep(3000);
     const configAccount = await CheckCtx.config(ctx);
 
     const currentSeasonId = configAccount.currentSeasonId;
 
-    const seasonIdBuffer = Buffer.from([currentSeasonId + 1]);
+    const seasonIdBuffer = seasonIdSeed(currentSeasonId + 1);
 
     ctx.seasonAccount = PublicKey.findProgramAddressSync(
       [Buffer.from("season"), seasonIdBuffer],
@@ -305,7 +880,7 @@ describe("fomolove-sonic", () => {
     const startTime = new anchor.BN(Date.now() / 1000);
 
     try {
-      await program.methods.startSeason(startTime).accountsPartial({
+      await program.methods.startSeason(startTime, SEASON_PARAMS).accountsPartial({
         maintainer: ctx.maintainer.publicKey,
         configAccount: ctx.configAccount,
         seasonAccount: ctx.seasonAccount,
@@ -316,4 +891,250 @@ describe("fomolove-sonic", () => {
       expect(err.error.errorMessage).to.equal('SeasonNotEnded')
     }
   });
+
+  it("Cannot schedule blockers without a lifetime", async () => {
+    const seasonSchedule = PublicKey.findProgramAddressSync(
+      [Buffer.from("season_schedule")],
+      program.programId
+    )[0];
+    const config = await CheckCtx.config(ctx);
+    const params = { ...SEASON_PARAMS, specialTiles: { ...SPECIAL_TILES, blockerLifetime: 0 } };
+
+    try {
+      await program.methods.scheduleSeasons([
+        { startTime: config.currentSeasonEndedAt, params, leaderboards: Array(4).fill(PublicKey.default) },
+      ], new anchor.BN(CRANK_REWARD)).accountsPartial({
+        maintainer: ctx.maintainer.publicKey,
+        configAccount: ctx.configAccount,
+        seasonSchedule,
+      }).signers([ctx.maintainer]).rpc();
+      assert.fail("scheduled invalid special tiles");
+    } catch (error) {
+      assert.isTrue(error instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = error;
+      expect(err.error.errorCode.code).to.equal('InvalidSpecialTiles')
+    }
+  });
+
+  it("Schedule the next seasons!", async () => {
+    const seasonSchedule = PublicKey.findProgramAddressSync(
+      [Buffer.from("season_schedule")],
+      program.programId
+    )[0];
+    const config = await CheckCtx.config(ctx);
+    const startTime = config.currentSeasonEndedAt;
+    scheduledLeaderboards = [
+      await createScheduledLeaderboards(SEASON_PARAMS),
+      await createScheduledLeaderboards(SPECIAL_SEASON_PARAMS),
+    ];
+
+    await program.methods.scheduleSeasons([
+      { startTime, params: SEASON_PARAMS, leaderboards: scheduledLeaderboards[0] },
+      {
+        startTime: startTime.add(new anchor.BN(24 * 60 * 60)),
+        params: SPECIAL_SEASON_PARAMS,
+        leaderboards: scheduledLeaderboards[1],
+      },
+    ], new anchor.BN(CRANK_REWARD)).accountsPartial({
+      maintainer: ctx.maintainer.publicKey,
+      configAccount: ctx.configAccount,
+      seasonSchedule,
+    }).remainingAccounts(
+      leaderboardMetas(scheduledLeaderboards[0].concat(scheduledLeaderboards[1]), false)
+    ).signers([ctx.maintainer]).rpc();
+
+    const schedule = await program.account.seasonScheduleAccount.fetch(seasonSchedule);
+    expect(schedule.seasons.length).to.equal(2);
+    expect(schedule.seasons[0].leaderboards.map((key) => key.toBase58()))
+      .to.deep.equal(scheduledLeaderboards[0].map((key) => key.toBase58()));
+    expect(schedule.crankReward.toNumber()).to.equal(CRANK_REWARD);
+    expect(schedule.seasons[0].params.specialTiles).to.deep.equal(NO_SPECIAL_TILES);
+    expect(schedule.seasons[1].params.specialTiles).to.deep.equal(SPECIAL_TILES);
+  });
+
+  it("Unschedule a season the crank could not open", async () => {
+    const seasonSchedule = PublicKey.findProgramAddressSync(
+      [Buffer.from("season_schedule")],
+      program.programId
+    )[0];
+    const before = await program.account.seasonScheduleAccount.fetch(seasonSchedule);
+    const leaderboards = await createScheduledLeaderboards(SEASON_PARAMS);
+
+    await program.methods.scheduleSeasons([
+      {
+        startTime: before.seasons[1].startTime.add(new anchor.BN(24 * 60 * 60)),
+        params: SEASON_PARAMS,
+        leaderboards,
+      },
+    ], new anchor.BN(CRANK_REWARD)).accountsPartial({
+      maintainer: ctx.maintainer.publicKey,
+      configAccount: ctx.configAccount,
+      seasonSchedule,
+    }).remainingAccounts(
+      leaderboardMetas(leaderboards, false)
+    ).signers([ctx.maintainer]).rpc();
+
+    try {
+      await program.methods.unscheduleSeason(2).accountsPartial({
+        maintainer: ctx.user1.publicKey,
+        configAccount: ctx.configAccount,
+        seasonSchedule,
+      }).signers([ctx.user1]).rpc();
+      assert.fail("unscheduled a season without being the maintainer");
+    } catch (error) {
+      assert.isTrue(error instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = error;
+      expect(err.error.errorCode.code).to.equal('Unauthorized')
+    }
+
+    try {
+      await program.methods.unscheduleSeason(3).accountsPartial({
+        maintainer: ctx.maintainer.publicKey,
+        configAccount: ctx.configAccount,
+        seasonSchedule,
+      }).signers([ctx.maintainer]).rpc();
+      assert.fail("unscheduled a season past the end of the schedule");
+    } catch (error) {
+      assert.isTrue(error instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = error;
+      expect(err.error.errorCode.code).to.equal('ScheduledSeasonNotFound')
+    }
+
+    await program.methods.unscheduleSeason(2).accountsPartial({
+      maintainer: ctx.maintainer.publicKey,
+      configAccount: ctx.configAccount,
+      seasonSchedule,
+    }).signers([ctx.maintainer]).rpc();
+
+    const schedule = await program.account.seasonScheduleAccount.fetch(seasonSchedule);
+    expect(schedule.seasons.length).to.equal(2);
+    expect(schedule.seasons[1].leaderboards.map((key) => key.toBase58()))
+      .to.deep.equal(scheduledLeaderboards[1].map((key) => key.toBase58()));
+  });
+
+  it("Cannot crank the next season before the current one ends", async () => {
+    try {
+      await program.methods.crankNextSeason().accountsPartial({
+        cranker: ctx.user2.publicKey,
+        configAccount: ctx.configAccount,
+        seasonAccount: ctx.seasonAccount,
+      }).remainingAccounts(
+        leaderboardMetas(scheduledLeaderboards[0], true)
+      ).signers([ctx.user2]).rpc();
+      assert.fail("cranked a season early");
+    } catch (error) {
+      assert.isTrue(error instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = error;
+      expect(err.error.errorCode.code).to.equal('SeasonNotEnded')
+    }
+  });
+
+  it("Paused seasons hold back the crank and expiry until they resume and end", async () => {
+    const config = await CheckCtx.config(ctx);
+    const seasonIdBuffer = seasonIdSeed(config.currentSeasonId);
+    const seasonAccount = PublicKey.findProgramAddressSync(
+      [Buffer.from("season"), seasonIdBuffer],
+      program.programId
+    )[0];
+    const seasonResult = PublicKey.findProgramAddressSync(
+      [Buffer.from("season_result"), seasonIdBuffer],
+      program.programId
+    )[0];
+    const chainTeamSeason = PublicKey.findProgramAddressSync(
+      [Buffer.from("team_season"), Buffer.from([2]), seasonIdBuffer],
+      program.programId
+    )[0];
+
+    await program.methods.updateSeasonEnd(new anchor.BN(Math.floor(Date.now() / 1000) + 3)).accountsPartial({
+      maintainer: ctx.maintainer.publicKey,
+      configAccount: ctx.configAccount,
+      seasonAccount,
+    }).signers([ctx.maintainer]).rpc();
+    await program.methods.pauseSeason().accountsPartial({
+      maintainer: ctx.maintainer.publicKey,
+      configAccount: ctx.configAccount,
+      seasonAccount,
+    }).signers([ctx.maintainer]).rpc();
+    expect((await CheckCtx.config(ctx)).currentSeasonEndedAt.toString()).to.equal("18446744073709551615");
+
+    // Past the end the season had when it was paused
+    await sleep(5000);
+    try {
+      await program.methods.expireGame().accountsPartial({
+        signer: ctx.user2.publicKey,
+        seasonAccount,
+        gameAccount: unsubmittedGame,
+      }).signers([ctx.user2]).rpc();
+      assert.fail("expired a game of a paused season");
+    } catch (error) {
+      assert.isTrue(error instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = error;
+      expect(err.error.errorCode.code).to.equal('SeasonPaused')
+    }
+
+    try {
+      await program.methods.crankNextSeason().accountsPartial({
+        cranker: ctx.user2.publicKey,
+        configAccount: ctx.configAccount,
+        seasonAccount: ctx.seasonAccount,
+      }).remainingAccounts(
+        leaderboardMetas(scheduledLeaderboards[0], true)
+      ).signers([ctx.user2]).rpc();
+      assert.fail("cranked a season while the current one is paused");
+    } catch (error) {
+      assert.isTrue(error instanceof anchor.AnchorError);
+      const err: anchor.AnchorError = error;
+      expect(err.error.errorCode.code).to.equal('SeasonNotEnded')
+    }
+
+    await program.methods.resumeSeason().accountsPartial({
+      maintainer: ctx.maintainer.publicKey,
+      configAccount: ctx.configAccount,
+      seasonAccount,
+    }).signers([ctx.maintainer]).rpc();
+    const resumed = await program.account.seasonAccount.fetch(seasonAccount);
+    expect(resumed.pausedTime.toNumber()).to.be.at.least(5);
+    expect(resumed.endedAt.toNumber()).to.be.greaterThan(Date.now() / 1000);
+    await sleep(5000);
+
+    await program.methods.expireGame().accountsPartial({
+      signer: ctx.user2.publicKey,
+      seasonAccount,
+      gameAccount: unsubmittedGame,
+    }).signers([ctx.user2]).rpc();
+    expect((await program.account.gameAccount.fetch(unsubmittedGame)).status).to.deep.equal({ expired: {} });
+
+    await program.methods.finalizeSeason().accountsPartial({
+      payer: ctx.user2.publicKey,
+      seasonAccount,
+      seasonResult,
+      memeTeamSeason: ctx.memeTeamSeasonAccount,
+      chainTeamSeason,
+    }).remainingAccounts([
+      { pubkey: ctx.classicLeaderboard.publicKey, isWritable: false, isSigner: false },
+    ]).signers([ctx.user2]).rpc();
+    expect((await program.account.seasonAccount.fetch(seasonAccount)).finalized).to.equal(true);
+
+    await program.methods.withdrawEntryFees().accountsPartial({
+      maintainer: ctx.maintainer.publicKey,
+      configAccount: ctx.configAccount,
+      seasonAccount,
+      seasonResult,
+      recipient: ctx.maintainer.publicKey,
+    }).signers([ctx.maintainer]).rpc();
+    expect((await program.account.seasonAccount.fetch(seasonAccount)).entryFees.toNumber()).to.equal(0);
+
+    await program.methods.crankNextSeason().accountsPartial({
+      cranker: ctx.user2.publicKey,
+      configAccount: ctx.configAccount,
+      seasonAccount: ctx.seasonAccount,
+    }).remainingAccounts(
+      leaderboardMetas(scheduledLeaderboards[0], true)
+    ).signers([ctx.user2]).rpc();
+
+    const cranked = await CheckCtx.season(ctx);
+    expect(cranked.seasonId).to.equal(config.currentSeasonId + 1);
+    expect(cranked.leaderboards.map((key) => key.toBase58()))
+      .to.deep.equal(scheduledLeaderboards[0].map((key) => key.toBase58()));
+  });
 });
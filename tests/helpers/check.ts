@@ -1,8 +1,20 @@
 import * as anchor from "@coral-xyz/anchor";
 
+import { PublicKey } from "@solana/web3.js";
 import { Ctx } from "./ctx";
 
 type Balance = number | anchor.BN | bigint;
+
+// Two high bits are the tile kind, the six low bits its exponent or blocker lifetime
+function cellLabel(cell: number): number | string {
+    const payload = cell & 0x3f;
+    switch (cell >> 6) {
+        case 1: return `x2 ${2 ** payload}`;
+        case 2: return "wild";
+        case 3: return `block ${payload}`;
+        default: return payload === 0 ? 0 : 2 ** payload;
+    }
+}
 export namespace CheckCtx {
 
     export async function config(ctx: Ctx) {
@@ -30,8 +42,37 @@ export namespace CheckCtx {
         return user;
     }
 
+    export async function gameLog(ctx: Ctx) {
+        const gameLog = await ctx.program.account.gameLogAccount.fetch(ctx.gameLogAccount);
+        return gameLog;
+    }
+
+    // Zero-copy leaderboard: 8 byte discriminator, 56 byte header, then 88 byte games
+    export async function seasonLeaderboardGames(ctx: Ctx, leaderboard: PublicKey) {
+        const header = await ctx.program.account.seasonLeaderboardAccount.fetch(leaderboard);
+        const info = await ctx.connection.getAccountInfo(leaderboard);
+        const games = [];
+        for (let i = 0; i < header.gameCount; i++) {
+            const offset = 64 + i * 88;
+            games.push({
+                game: new PublicKey(info.data.subarray(offset, offset + 32)),
+                player: new PublicKey(info.data.subarray(offset + 32, offset + 64)),
+                score: info.data.readBigUInt64LE(offset + 64),
+                topTile: info.data.readUInt32LE(offset + 72),
+                moveCount: info.data.readUInt32LE(offset + 76),
+            });
+        }
+        return games;
+    }
+
     export async function getGameState(ctx: Ctx) {
         const gameState = await ctx.program.account.gameAccount.fetch(ctx.gameAccount);
-        console.table(gameState.board);
+        // The board stores row-major tile exponents
+        const size = gameState.boardSize;
+        const rows = [];
+        for (let i = 0; i < size; i++) {
+            rows.push(gameState.board.slice(i * size, (i + 1) * size).map(cellLabel));
+        }
+        console.table(rows);
     }
 }
\ No newline at end of file
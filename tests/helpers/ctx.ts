@@ -21,7 +21,12 @@ export interface Ctx {
   user1Account: PublicKey,
   user2Account: PublicKey,
   user1SeasonAccount: PublicKey,
+  memeTeamSeasonAccount: PublicKey,
   gameAccount: PublicKey,
+  gameLogAccount: PublicKey,
+  dailyChallenge: PublicKey,
+  classicLeaderboard: Keypair,
+  user1ClassicEntries: PublicKey,
   nftMint: Keypair,
   nftTokenAccount: PublicKey
 }
@@ -60,6 +65,11 @@ export async function createCtx(connection: Connection, program: Program<Fomolov
     [Buffer.from("game"), nftMint.publicKey.toBuffer()],
     program.programId
   )[0];
+
+  const gameLogAccount = PublicKey.findProgramAddressSync(
+    [Buffer.from("game_log"), gameAccount.toBuffer()],
+    program.programId
+  )[0];
   
   const user1Account =  PublicKey.findProgramAddressSync(
     [Buffer.from("user"), user1.publicKey.toBuffer()],
@@ -72,6 +82,12 @@ export async function createCtx(connection: Connection, program: Program<Fomolov
   )[0];
   const user1SeasonAccount = new PublicKey(bs58.encode(buffer));
 
+  const classicLeaderboard = Keypair.generate();
+  const user1ClassicEntries = PublicKey.findProgramAddressSync(
+    [Buffer.from("leaderboard_player"), classicLeaderboard.publicKey.toBuffer(), user1.publicKey.toBuffer()],
+    program.programId
+  )[0];
+
   return {
     connection, 
     program,
@@ -86,7 +102,12 @@ export async function createCtx(connection: Connection, program: Program<Fomolov
     user1Account,
     user2Account,
     user1SeasonAccount,
+    memeTeamSeasonAccount: new PublicKey(bs58.encode(buffer)),
     gameAccount,
+    gameLogAccount,
+    dailyChallenge: new PublicKey(bs58.encode(buffer)),
+    classicLeaderboard,
+    user1ClassicEntries,
     nftMint,
     nftTokenAccount
   }
@@ -49,3 +49,10 @@ export async function sleepTill(tillMs) {
       await sleep(tillMs - Date.now());
   }
 }
+
+// Season ids are u32, seeded as little-endian bytes
+export function seasonIdSeed(seasonId: number): Buffer {
+  const seed = Buffer.alloc(4);
+  seed.writeUInt32LE(seasonId);
+  return seed;
+}